@@ -0,0 +1,500 @@
+//! Types shared by the attacker programs in this workspace. Unlike
+//! `test-utils`, everything here is compiled into on-chain code, so it is
+//! limited to plain data definitions with Anchor serialization.
+
+use anchor_lang::prelude::*;
+
+/// Uniform attack result reported by attacker entrypoints through return
+/// data, so one test harness can interpret every attacker the same way
+/// instead of learning each program's private logging format.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct Outcome {
+    /// Whether the exploit observably landed against the target account.
+    pub exploited: bool,
+    /// Human-readable explanation of what the attacker observed.
+    pub detail: String,
+}
+
+impl Outcome {
+    /// The exploit landed: the target account shows attacker-controlled state.
+    pub fn exploited(detail: impl Into<String>) -> Self {
+        Self {
+            exploited: true,
+            detail: detail.into(),
+        }
+    }
+
+    /// The exploit was blocked (or the target wasn't what the attacker
+    /// expected): the target account shows no attacker influence.
+    pub fn blocked(detail: impl Into<String>) -> Self {
+        Self {
+            exploited: false,
+            detail: detail.into(),
+        }
+    }
+}
+
+/// Everything a harness observed about the victim account after letting an
+/// attacker run: the account's raw bytes, its lamport balances around the
+/// attack, and who signed. Expectations captured *before* the attack (a
+/// pre-attack balance, the fee the attacker tried to set) live on the
+/// individual [`Exploit`] value, not here.
+pub struct ExploitCtx<'a> {
+    /// Raw data of the targeted account after the attack transaction.
+    pub target_data: &'a [u8],
+    /// Lamports on the targeted account (before, after) the attack.
+    pub target_lamports: (u64, u64),
+    /// The key the attacker signed with.
+    pub attacker: Pubkey,
+}
+
+/// Return type of [`Exploit::run`]. Aliased so attacker crates pinned to a
+/// different anchor-lang major can still spell the trait signature without
+/// naming this crate's `Result` directly.
+pub type ExploitResult = Result<Outcome>;
+
+/// One registered exploit. Each attacker program implements this for its
+/// primary instruction, so a driver can iterate `Vec<Box<dyn Exploit>>`
+/// over every victim in the workspace instead of calling five bespoke
+/// assessment APIs.
+pub trait Exploit {
+    /// Short label for harness output, e.g. `"authority-escalation"`.
+    fn name(&self) -> &'static str;
+
+    /// Judges the post-attack observations and reports whether the exploit
+    /// observably landed.
+    fn run(&self, ctx: &ExploitCtx) -> ExploitResult;
+}
+
+/// Objective pass/fail judge for one attack, driven purely by post-state.
+///
+/// Each attacker program also writes a `succeeded` flag into its own
+/// `AttackLog` — but that flag is self-reported, and an attacker program is
+/// the last thing a harness should take at its word. Implementations of
+/// this trait answer the question from the victim's side instead: did the
+/// fee actually change, did the pause flag actually flip, did the balance
+/// actually wrap, were the bytes actually overwritten. [`Exploit::run`]
+/// layers a human-readable [`Outcome`] on top of the same evidence; this is
+/// the bare verdict for code that only needs the boolean.
+pub trait SuccessOracle {
+    /// Inspects the post-attack observations and reports whether the
+    /// victim account really shows attacker influence.
+    fn succeeded(&self, ctx: &ExploitCtx) -> bool;
+}
+
+/// Structured self-description of one vulnerability example. Each vuln
+/// program exposes a `VULN_INFO` constant plus a `describe()` wrapper, so
+/// summaries are generated from code instead of hand-maintained prose that
+/// drifts from what the program actually does.
+pub struct VulnInfo {
+    /// Short label matching the example directory, e.g. "cpi-reentrancy".
+    pub name: &'static str,
+    /// Severity bucket: "critical", "high", "medium" or "low".
+    pub severity: &'static str,
+    /// One-paragraph account of how the attack lands.
+    pub summary: &'static str,
+    /// One-paragraph account of what the fix program changes.
+    pub mitigation: &'static str,
+}
+
+impl VulnInfo {
+    /// Renders the metadata as a markdown section a doc generator can embed
+    /// verbatim: title with severity, then attack and mitigation blocks.
+    pub fn describe(&self) -> String {
+        format!(
+            "# {} (severity: {})\n\n## Attack\n\n{}\n\n## Mitigation\n\n{}\n",
+            self.name, self.severity, self.summary, self.mitigation
+        )
+    }
+}
+
+/// Matches an account's leading 8 bytes against a list of known Anchor
+/// discriminators, returning the index of the matching candidate.
+///
+/// Attackers inspect arbitrary victim accounts before committing to an
+/// exploit: "is this a `Vault`, a `Config`, or something else entirely?"
+/// Each attacker program previously answered that with its own hardcoded
+/// prefix comparison; this is the shared form. Returns `None` for accounts
+/// that are too short, already borrowed, or match no candidate.
+pub fn try_identify(info: &AccountInfo, candidates: &[[u8; 8]]) -> Option<usize> {
+    let data = info.try_borrow_data().ok()?;
+    if data.len() < 8 {
+        return None;
+    }
+    candidates.iter().position(|disc| data[..8] == disc[..])
+}
+
+/// Error raised by [`ensure_not_paused`] while the global pause is engaged.
+#[error_code]
+pub enum PauseError {
+    #[msg("the protocol is paused; state-changing instructions are disabled")]
+    ProtocolPaused,
+}
+
+/// Anything carrying a global pause flag. The signer-privilege example's
+/// `Settings` is the canonical implementor; the trait exists so this crate
+/// does not have to depend on that program crate.
+pub trait Pausable {
+    fn is_paused(&self) -> bool;
+}
+
+/// The pausable-modifier pattern: fix programs call this at the top of every
+/// state-changing instruction so one admin-controlled flag halts all of them
+/// at once. Like [`ReentrancyGuard`], the value of routing through a shared
+/// helper is uniformity — a pause that some instructions forget to consult
+/// is not a pause, it is a suggestion.
+pub fn ensure_not_paused(settings: &impl Pausable) -> Result<()> {
+    require!(!settings.is_paused(), PauseError::ProtocolPaused);
+    Ok(())
+}
+
+/// Errors raised by [`parse_amount`] when a user-facing amount cannot be
+/// converted into base units.
+#[error_code]
+pub enum AmountError {
+    #[msg("the amount does not fit in a u64 once scaled to base units")]
+    AmountTooLarge,
+    #[msg("no supported token uses this many decimals")]
+    UnsupportedDecimals,
+}
+
+/// Converts a user-facing whole-token amount into base units, refusing any
+/// conversion that would overflow.
+///
+/// Instruction arguments arrive in whatever unit the client found
+/// convenient; scaling them by `10^decimals` with a raw `*` is exactly the
+/// wrap-or-panic hazard the arithmetic example warns about, hit before the
+/// handler's balance math even starts. Every fix program converts through
+/// here instead, so the overflow check exists once rather than per handler.
+/// `10^20` already exceeds u64, so any `decimals` above 19 is refused as
+/// unsupported before the multiplication is attempted.
+pub fn parse_amount(raw: u64, decimals: u8) -> Result<u64> {
+    let scale = 10u64
+        .checked_pow(u32::from(decimals))
+        .ok_or(AmountError::UnsupportedDecimals)?;
+    raw.checked_mul(scale)
+        .ok_or_else(|| AmountError::AmountTooLarge.into())
+}
+
+/// Where "now" comes from. On-chain that is the Clock sysvar via
+/// [`SysvarClock`]; in unit tests it is whatever instant the test injects
+/// via [`FixedClock`]. Handlers that take time-based decisions (timelocks,
+/// cooldowns, staleness windows) accept `&impl TimeSource` instead of
+/// calling `Clock::get()` inline, the same inversion [`Pausable`] uses —
+/// the logic depends on the capability, not on the sysvar, so it runs in
+/// tests where no sysvar exists.
+pub trait TimeSource {
+    /// The current unix timestamp in seconds.
+    fn now(&self) -> Result<i64>;
+}
+
+/// The production [`TimeSource`]: reads the Clock sysvar. Off-chain this
+/// errors the same way a bare `Clock::get()` would, which is exactly why
+/// tests inject [`FixedClock`] instead.
+pub struct SysvarClock;
+
+impl TimeSource for SysvarClock {
+    fn now(&self) -> Result<i64> {
+        Ok(Clock::get()?.unix_timestamp)
+    }
+}
+
+/// A [`TimeSource`] pinned to one instant — the unit-test stand-in for
+/// `solana-program-test`'s clock warping. Tests drive a cooldown through
+/// its whole lifecycle by calling the same helper with successive
+/// `FixedClock` values.
+pub struct FixedClock(pub i64);
+
+impl TimeSource for FixedClock {
+    fn now(&self) -> Result<i64> {
+        Ok(self.0)
+    }
+}
+
+/// Error raised by [`ensure_cooldown_elapsed`] while the window is still
+/// running.
+#[error_code]
+pub enum CooldownError {
+    #[msg("the cooldown window has not elapsed yet")]
+    CooldownActive,
+}
+
+/// Shared cooldown gate: refuses until at least `cooldown_secs` have passed
+/// since `last_ts`, and on success returns the instant the caller should
+/// store as the new `last_ts`. Returning the timestamp (instead of having
+/// the caller read the clock a second time) keeps the stored value and the
+/// one the decision was taken against identical. The subtraction saturates
+/// so a corrupt far-future `last_ts` cannot overflow; a backwards clock
+/// simply yields a negative elapsed time, which never satisfies a
+/// non-negative window.
+pub fn ensure_cooldown_elapsed(
+    last_ts: i64,
+    cooldown_secs: i64,
+    time: &impl TimeSource,
+) -> Result<i64> {
+    let now = time.now()?;
+    require!(
+        now.saturating_sub(last_ts) >= cooldown_secs,
+        CooldownError::CooldownActive
+    );
+    Ok(now)
+}
+
+/// Error raised by [`ReentrancyGuard`] when an instruction is entered while
+/// another guarded instruction is still in flight.
+#[error_code]
+pub enum GuardError {
+    #[msg("re-entrancy blocked: another guarded instruction is in flight")]
+    ReentrancyBlocked,
+}
+
+/// Uniform re-entrancy guard over a lock flag stored in account state.
+///
+/// The classic bypass is a lock that is *set* by one instruction but only
+/// *checked* by some of the others — any unchecked path mutates state right
+/// through the guard. Routing every balance-mutating instruction through
+/// `enter`/`exit` makes the check impossible to forget on one path.
+pub struct ReentrancyGuard;
+
+impl ReentrancyGuard {
+    /// Refuses entry while the lock is held, then takes it.
+    pub fn enter(lock: &mut bool) -> Result<()> {
+        require!(!*lock, GuardError::ReentrancyBlocked);
+        *lock = true;
+        Ok(())
+    }
+
+    /// Releases the lock after the instruction's work is done.
+    pub fn exit(lock: &mut bool) {
+        *lock = false;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct FakeSettings {
+        paused: bool,
+    }
+
+    impl Pausable for FakeSettings {
+        fn is_paused(&self) -> bool {
+            self.paused
+        }
+    }
+
+    #[test]
+    fn pause_helper_blocks_only_while_paused() {
+        ensure_not_paused(&FakeSettings { paused: false }).unwrap();
+
+        let err = ensure_not_paused(&FakeSettings { paused: true }).unwrap_err();
+        assert!(format!("{}", err).contains("paused"));
+    }
+
+    /// Scaling across the supported range, including both refusal modes:
+    /// an amount that overflows once scaled, and a decimals value no token
+    /// uses whose scale factor itself exceeds u64.
+    #[test]
+    fn parse_amount_scales_and_refuses_overflow() {
+        // Ordinary conversions at common token scales.
+        assert_eq!(parse_amount(5, 9).unwrap(), 5_000_000_000);
+        assert_eq!(parse_amount(1, 0).unwrap(), 1);
+        assert_eq!(parse_amount(0, 19).unwrap(), 0);
+
+        // The largest 9-decimal amount that still fits, and one past it.
+        let max_whole = u64::MAX / 1_000_000_000;
+        parse_amount(max_whole, 9).unwrap();
+        let err = parse_amount(max_whole + 1, 9).unwrap_err();
+        assert!(format!("{}", err).contains("does not fit in a u64"));
+
+        // 10^20 > u64::MAX: the scale factor itself is unrepresentable.
+        let err = parse_amount(1, 20).unwrap_err();
+        assert!(format!("{}", err).contains("this many decimals"));
+    }
+
+    /// A full cooldown lifecycle with injected times: blocked inside the
+    /// window, released exactly at the boundary, and re-armed by storing
+    /// the timestamp the helper hands back.
+    #[test]
+    fn cooldown_releases_at_the_boundary_and_rearms() {
+        let mut last_ts = 1_000i64;
+        let cooldown = 60i64;
+
+        // 30s in: still cooling down.
+        let err = ensure_cooldown_elapsed(last_ts, cooldown, &FixedClock(1_030)).unwrap_err();
+        assert!(format!("{}", err).contains("cooldown window has not elapsed"));
+
+        // One second short of the window: still refused.
+        assert!(ensure_cooldown_elapsed(last_ts, cooldown, &FixedClock(1_059)).is_err());
+
+        // Exactly at the boundary: allowed, and the helper returns the new
+        // anchor to store.
+        last_ts = ensure_cooldown_elapsed(last_ts, cooldown, &FixedClock(1_060)).unwrap();
+        assert_eq!(last_ts, 1_060);
+
+        // The stored instant re-arms the window from there.
+        assert!(ensure_cooldown_elapsed(last_ts, cooldown, &FixedClock(1_061)).is_err());
+        assert!(ensure_cooldown_elapsed(last_ts, cooldown, &FixedClock(1_120)).is_ok());
+    }
+
+    /// A clock that runs backwards (or a corrupt `last_ts` from the future)
+    /// yields a NEGATIVE elapsed time, which can never reach a non-negative
+    /// window — the gate errs on the refusing side until real time catches
+    /// up with the stored anchor.
+    #[test]
+    fn cooldown_never_unlocks_on_a_backwards_clock() {
+        assert!(ensure_cooldown_elapsed(2_000, 60, &FixedClock(1_000)).is_err());
+        // Even a zero-length window refuses while elapsed is negative...
+        assert!(ensure_cooldown_elapsed(2_000, 0, &FixedClock(1_000)).is_err());
+        // ...and passes the moment the clock catches the anchor.
+        assert!(ensure_cooldown_elapsed(2_000, 0, &FixedClock(2_000)).is_ok());
+    }
+
+    /// Off-chain there is no Clock sysvar, so the production source errors
+    /// instead of fabricating a time — the reason handlers take the trait.
+    #[test]
+    fn sysvar_clock_errors_where_no_sysvar_exists() {
+        assert!(SysvarClock.now().is_err());
+        assert_eq!(FixedClock(42).now().unwrap(), 42);
+    }
+
+    #[test]
+    fn guard_blocks_nested_entry_and_releases_cleanly() {
+        let mut lock = false;
+
+        ReentrancyGuard::enter(&mut lock).unwrap();
+        // A nested entry while the lock is held must be refused.
+        assert!(ReentrancyGuard::enter(&mut lock).is_err());
+
+        ReentrancyGuard::exit(&mut lock);
+        // After exit the next (sequential) entry succeeds again.
+        ReentrancyGuard::enter(&mut lock).unwrap();
+        assert!(lock);
+    }
+
+    fn make_account(data: Vec<u8>) -> anchor_lang::solana_program::account_info::AccountInfo<'static> {
+        use anchor_lang::solana_program::clock::Epoch;
+
+        let key = Box::leak(Box::new(Pubkey::new_unique()));
+        let lamports = Box::leak(Box::new(1_000_000_000u64));
+        let data: &'static mut [u8] = Box::leak(data.into_boxed_slice());
+        let owner = Box::leak(Box::new(Pubkey::new_unique()));
+
+        anchor_lang::solana_program::account_info::AccountInfo::new(
+            key,
+            false,
+            true,
+            lamports,
+            data,
+            owner,
+            false,
+            Epoch::default(),
+        )
+    }
+
+    #[test]
+    fn identify_matches_known_discriminators() {
+        use anchor_lang::Discriminator;
+
+        let candidates: Vec<[u8; 8]> = vec![
+            <unsafe_arithmetic_fix::Vault as Discriminator>::DISCRIMINATOR
+                .try_into()
+                .unwrap(),
+            <incorrect_authority_fix::Config as Discriminator>::DISCRIMINATOR
+                .try_into()
+                .unwrap(),
+            <missing_account_fix::MessageBox as Discriminator>::DISCRIMINATOR
+                .try_into()
+                .unwrap(),
+        ];
+
+        for (expected, disc) in candidates.iter().enumerate() {
+            let mut data = disc.to_vec();
+            data.extend_from_slice(&[0u8; 16]); // arbitrary body bytes
+            let info = make_account(data);
+            assert_eq!(try_identify(&info, &candidates), Some(expected));
+        }
+    }
+
+    #[test]
+    fn identify_returns_none_for_unknown_or_short_accounts() {
+        use anchor_lang::Discriminator;
+
+        let candidates: Vec<[u8; 8]> = vec![
+            <unsafe_arithmetic_fix::Vault as Discriminator>::DISCRIMINATOR
+                .try_into()
+                .unwrap(),
+        ];
+
+        // A discriminator none of the candidates carry.
+        let info = make_account([9u8; 24].to_vec());
+        assert_eq!(try_identify(&info, &candidates), None);
+
+        // Fewer than 8 bytes cannot hold a discriminator at all.
+        let info = make_account(vec![1, 2, 3]);
+        assert_eq!(try_identify(&info, &candidates), None);
+    }
+
+    /// The doc generator's contract: every vuln program self-describes, and
+    /// the rendered text is non-empty and carries the severity and name so
+    /// an index page can be assembled from `describe()` output alone.
+    #[test]
+    fn every_vuln_program_renders_a_complete_description() {
+        // (name, severity, rendered) — plain strings, so this compiles even
+        // though the dev-dependency cycle links a second copy of this crate
+        // whose `VulnInfo` is a distinct type from the one under test.
+        let entries = [
+            (
+                missing_account_vuln::VULN_INFO.name,
+                missing_account_vuln::VULN_INFO.severity,
+                missing_account_vuln::describe(),
+            ),
+            (
+                incorrect_authority_vuln::VULN_INFO.name,
+                incorrect_authority_vuln::VULN_INFO.severity,
+                incorrect_authority_vuln::describe(),
+            ),
+            (
+                unsafe_arithmetic_vuln::VULN_INFO.name,
+                unsafe_arithmetic_vuln::VULN_INFO.severity,
+                unsafe_arithmetic_vuln::describe(),
+            ),
+            (
+                cpi_reentrancy_vuln::VULN_INFO.name,
+                cpi_reentrancy_vuln::VULN_INFO.severity,
+                cpi_reentrancy_vuln::describe(),
+            ),
+            (
+                signer_privilege_vuln::VULN_INFO.name,
+                signer_privilege_vuln::VULN_INFO.severity,
+                signer_privilege_vuln::describe(),
+            ),
+        ];
+
+        for (name, severity, rendered) in entries {
+            assert!(!rendered.is_empty(), "{} rendered empty", name);
+            assert!(rendered.contains(severity), "{} lacks its severity", name);
+            assert!(rendered.contains(name));
+            assert!(rendered.contains("## Attack") && rendered.contains("## Mitigation"));
+            // The severity buckets are a closed set the index sorts by.
+            assert!(matches!(severity, "critical" | "high" | "medium" | "low"));
+        }
+    }
+
+    #[test]
+    fn outcome_roundtrips_through_return_data_encoding() {
+        // A harness reads outcomes back from raw return-data bytes; the
+        // Borsh roundtrip is exactly that decode path.
+        let outcome = Outcome::exploited("fee changed by non-admin");
+        let bytes = outcome.try_to_vec().unwrap();
+        let decoded = Outcome::try_from_slice(&bytes).unwrap();
+        assert_eq!(decoded, outcome);
+        assert!(decoded.exploited);
+
+        let blocked = Outcome::blocked("has_one rejected the signer");
+        let decoded = Outcome::try_from_slice(&blocked.try_to_vec().unwrap()).unwrap();
+        assert!(!decoded.exploited);
+        assert_eq!(decoded.detail, "has_one rejected the signer");
+    }
+}
@@ -57,6 +57,79 @@ fn safe_withdraw(
     Ok(new_balance)
 }
 
+// Partial fix: reorder so the balance is debited BEFORE the external call,
+// but keep no lock. This alone defeats the classic same-function drain —
+// the re-entrant call sees the already-decremented balance — which is why
+// "just follow CEI" is such common advice. The cross-function model below
+// shows where that advice runs out.
+fn cei_only_withdraw(
+    state: &mut VaultState,
+    amount: u64,
+    attacker: impl FnOnce(&mut VaultState) -> Result<(), &'static str>,
+) -> Result<u64, &'static str> {
+    // Effects first: the debit is committed before anyone else runs.
+    let new_balance = state
+        .balance
+        .checked_sub(amount)
+        .ok_or("insufficient funds")?;
+    state.balance = new_balance;
+
+    // Interaction last. Any re-entry into THIS function now operates on the
+    // updated balance, so it cannot double-spend the same funds.
+    attacker(state)?;
+
+    Ok(state.balance)
+}
+
+// A vault whose redemption must touch two fields that only agree between
+// instructions: `shares` mirrors `balance` (one share per lamport) except
+// while a redemption is in flight.
+#[derive(Clone, Debug)]
+struct SharesVault {
+    balance: u64,
+    shares: u64,
+    in_flight: bool,
+}
+
+// CEI-ordered for `balance`, yet structurally unable to be CEI-ordered for
+// `shares`: the share burn settles only after the transfer confirms (the
+// real-world analogue is a CPI whose outcome decides the final state). No
+// lock, so the invariant break mid-call is observable from other paths.
+fn cei_only_redeem(
+    vault: &mut SharesVault,
+    amount: u64,
+    hook: impl FnOnce(&mut SharesVault) -> Result<(), &'static str>,
+) -> Result<(), &'static str> {
+    vault.balance = vault
+        .balance
+        .checked_sub(amount)
+        .ok_or("insufficient funds")?;
+
+    // External call while shares > balance.
+    hook(vault)?;
+
+    // Shares settle only now.
+    vault.shares = vault.shares.saturating_sub(amount);
+    Ok(())
+}
+
+// Unrelated entry point that pays out "yield": any excess of shares over
+// balance. Correct between transactions; exploitable during one.
+fn claim_yield(vault: &mut SharesVault) -> u64 {
+    let payout = vault.shares.saturating_sub(vault.balance);
+    vault.shares -= payout;
+    payout
+}
+
+// The lock-protected form: the same payout logic, but it refuses to run
+// while a redemption is in flight. This is the piece CEI cannot replace.
+fn claim_yield_locked(vault: &mut SharesVault) -> Result<u64, &'static str> {
+    if vault.in_flight {
+        return Err("reentrancy");
+    }
+    Ok(claim_yield(vault))
+}
+
 #[cfg(test)]
 mod pinocchio_tests {
     use super::*;
@@ -114,4 +187,73 @@ mod pinocchio_tests {
         assert_eq!(state.balance, 900);
         assert!(!state.is_locked);
     }
+
+    #[test]
+    fn cei_reorder_alone_blocks_the_classic_drain() {
+        let mut state = VaultState {
+            balance: 1_000,
+            is_locked: false,
+            authority: Pubkey::new_unique(),
+        };
+
+        // The attacker re-enters mid-call and tries the DAO-style move:
+        // withdraw the full original balance again. With the debit already
+        // committed, the nested call sees 900 and the double-spend fails.
+        let final_balance = cei_only_withdraw(&mut state, 100, |s| {
+            let err = cei_only_withdraw(s, 1_000, |_| Ok(())).unwrap_err();
+            assert_eq!(err, "insufficient funds");
+            Ok(())
+        })
+        .unwrap();
+
+        assert_eq!(final_balance, 900);
+        assert_eq!(state.balance, 900);
+
+        // A nested withdrawal of an amount the vault can actually cover is
+        // not theft — both debits land, exactly as sequential calls would:
+        // 900 - 100 - 500 = 300.
+        let final_balance = cei_only_withdraw(&mut state, 100, |s| {
+            cei_only_withdraw(s, 500, |_| Ok(())).map(|_| ())
+        })
+        .unwrap();
+        assert_eq!(final_balance, 300);
+        // Compare vuln_allows_double_spend_on_reentry, where the same two
+        // withdrawals leave the stale 900 on the books.
+    }
+
+    #[test]
+    fn cei_reorder_alone_still_loses_cross_function() {
+        let mut vault = SharesVault {
+            balance: 1_000,
+            shares: 1_000,
+            in_flight: false,
+        };
+
+        // Mid-redemption the balance is debited but the shares are not yet
+        // burned: shares - balance momentarily equals the in-flight amount.
+        // `claim_yield` follows CEI perfectly — and pays that gap out.
+        let mut stolen = 0;
+        cei_only_redeem(&mut vault, 100, |v| {
+            stolen = claim_yield(v);
+            Ok(())
+        })
+        .unwrap();
+
+        assert_eq!(stolen, 100);
+        // The books even re-balance afterwards, hiding the theft.
+        assert_eq!(vault.balance, 900);
+        assert_eq!(vault.shares, 800);
+
+        // A lock spanning both entry points is what closes this: with
+        // `in_flight` raised (as a locked redeem would do on entry), the
+        // same re-entry against the locked claim path is refused outright.
+        let mut vault = SharesVault {
+            balance: 1_000,
+            shares: 1_000,
+            in_flight: true,
+        };
+        let err = cei_only_redeem(&mut vault, 100, |v| claim_yield_locked(v).map(|_| ()))
+            .unwrap_err();
+        assert_eq!(err, "reentrancy");
+    }
 }
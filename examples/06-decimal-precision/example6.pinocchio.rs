@@ -0,0 +1,49 @@
+// Scale factor between a 9-decimal deposit token and the 6-decimal
+// accounting token: 10^(9-6) = 1000.
+const DECIMAL_FACTOR: u64 = 1_000;
+
+fn vuln_credit(amount_9dec: u64) -> u64 {
+    // Mirrors the vulnerable handler: divide then multiply, silently
+    // dropping the remainder in the protocol's favor.
+    (amount_9dec / DECIMAL_FACTOR) * DECIMAL_FACTOR
+}
+
+fn safe_credit(amount_9dec: u64) -> Result<u64, &'static str> {
+    // Strict policy variant of the fix: refuse amounts that would lose
+    // precision instead of silently rounding.
+    if amount_9dec % DECIMAL_FACTOR != 0 {
+        return Err("precision loss");
+    }
+    Ok(amount_9dec)
+}
+
+#[cfg(test)]
+mod pinocchio_tests {
+    use super::*;
+
+    #[test]
+    fn vuln_silently_rounds_non_divisible_amounts() {
+        let credited = vuln_credit(1_234_567);
+
+        // 567 base units vanished without any error.
+        assert_eq!(credited, 1_234_000);
+        assert_ne!(credited, 1_234_567);
+    }
+
+    #[test]
+    fn safe_rejects_non_divisible_and_accepts_divisible() {
+        let err = safe_credit(1_234_567).unwrap_err();
+        assert_eq!(err, "precision loss");
+
+        let ok = safe_credit(1_234_000).unwrap();
+        assert_eq!(ok, 1_234_000);
+    }
+
+    #[test]
+    fn both_agree_on_divisible_amounts() {
+        for amount in [0, 1_000, 42_000, 9_999_000] {
+            assert_eq!(vuln_credit(amount), amount);
+            assert_eq!(safe_credit(amount), Ok(amount));
+        }
+    }
+}
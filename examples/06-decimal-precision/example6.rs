@@ -0,0 +1,175 @@
+#![allow(unexpected_cfgs)]
+use anchor_lang::prelude::*;
+
+#[account]
+pub struct Ledger {
+    pub owner: Pubkey,
+    // Credited amount, denominated in the 6-decimal token's base units.
+    pub credited: u64,
+}
+
+declare_id!("a6YjJvCsuuVeMzTjn1NbJMnfjA4DPTYbWdZLDF4ZtwM");
+
+// Scale factor between a 9-decimal deposit token and the 6-decimal
+// accounting token: 10^(9-6) = 1000.
+pub const DECIMAL_FACTOR: u64 = 1_000;
+
+#[program]
+pub mod decimal_precision_vuln {
+    use super::*;
+
+    pub fn credit_deposit(ctx: Context<CreditDepositVuln>, amount_9dec: u64) -> Result<()> {
+        let ledger = &mut ctx.accounts.ledger;
+
+        // --- THE VULNERABILITY ---
+        // The deposit arrives in 9-decimal base units but the ledger is kept
+        // in 6-decimal units, so the program scales down and back up:
+        //
+        //   amount_9dec = 1_234_567  (0.001234567 tokens)
+        //   scaled      = 1_234_567 / 1000 = 1_234   (integer division!)
+        //   credited    = 1_234 * 1000     = 1_234_000
+        //
+        // The remainder (567 base units) silently vanishes — in the
+        // protocol's favor. Repeated over millions of deposits this skims
+        // real value from users, and auditors call it what it is: theft by
+        // rounding. The user receives NO error and NO refund.
+        let scaled = amount_9dec / DECIMAL_FACTOR;
+        let credited = scaled * DECIMAL_FACTOR;
+
+        ledger.credited += credited;
+
+        msg!("Credited {} of {} deposited units", credited, amount_9dec);
+        Ok(())
+    }
+
+    pub fn deposit_shares(ctx: Context<ShareOpVuln>, amount: u64) -> Result<()> {
+        let vault = &mut ctx.accounts.share_vault;
+
+        // The full amount lands in the vault, but the minted shares only
+        // represent the floored value — the difference just evaporates
+        // from the share side of the books.
+        vault.total_assets += amount;
+        vault.total_shares += shares_for_deposit_vuln(amount, vault.price_scaled);
+        Ok(())
+    }
+
+    pub fn redeem_shares(ctx: Context<ShareOpVuln>, shares: u64) -> Result<()> {
+        let vault = &mut ctx.accounts.share_vault;
+
+        // Exact shares are burned for a floored payout; the sub-unit value
+        // of the burned shares stays in the vault, uncounted.
+        vault.total_shares -= shares;
+        vault.total_assets -= assets_for_shares_vuln(shares, vault.price_scaled);
+        Ok(())
+    }
+
+    pub fn reconcile(ctx: Context<ShareOpVuln>) -> Result<u64> {
+        let vault = &ctx.accounts.share_vault;
+
+        // All a reconciler can do here is REPORT the damage. The flooring
+        // already destroyed the information an invariant would need, so
+        // there is no bound to enforce — contrast example6.fix.rs, where
+        // the carried dust makes the books balance to the unit.
+        let drift = books_drift_vuln(vault.total_assets, vault.total_shares, vault.price_scaled);
+        msg!("books drift by {} asset units", drift);
+        Ok(drift)
+    }
+}
+
+#[derive(Accounts)]
+pub struct CreditDepositVuln<'info> {
+    #[account(mut, has_one = owner)]
+    pub ledger: Account<'info, Ledger>,
+    pub owner: Signer<'info>,
+}
+
+// ---------------------------------------------------------------------------
+// The same disease in a share-based vault: every deposit and every redeem
+// floors a conversion, and nothing tracks what the floor threw away.
+// ---------------------------------------------------------------------------
+
+// Per-share price is quoted with 3 extra decimals: a `price_scaled` of 1_500
+// means one share is worth 1.5 asset units.
+pub const PRICE_SCALE: u64 = 1_000;
+
+#[account]
+pub struct ShareVault {
+    pub owner: Pubkey,
+    // Asset units the vault actually holds.
+    pub total_assets: u64,
+    // Shares outstanding against those assets.
+    pub total_shares: u64,
+    // Per-share price × PRICE_SCALE.
+    pub price_scaled: u64,
+}
+
+/// Shares minted for a deposit — floored, remainder discarded.
+pub fn shares_for_deposit_vuln(amount: u64, price_scaled: u64) -> u64 {
+    amount * PRICE_SCALE / price_scaled
+}
+
+/// Asset units paid out for redeemed shares — floored again.
+pub fn assets_for_shares_vuln(shares: u64, price_scaled: u64) -> u64 {
+    shares * price_scaled / PRICE_SCALE
+}
+
+/// The drift the vuln's books accumulate: the gap between the assets the
+/// vault records and the assets its outstanding shares can account for.
+pub fn books_drift_vuln(total_assets: u64, total_shares: u64, price_scaled: u64) -> u64 {
+    total_assets.abs_diff(assets_for_shares_vuln(total_shares, price_scaled))
+}
+
+#[derive(Accounts)]
+pub struct ShareOpVuln<'info> {
+    #[account(mut, has_one = owner)]
+    pub share_vault: Account<'info, ShareVault>,
+    pub owner: Signer<'info>,
+}
+
+/**
+ * SUMMARY OF THE BUG:
+ * 1. User deposits 1_234_567 base units of a 9-decimal token.
+ * 2. The program converts to 6-decimal accounting units with integer
+ *    division, dropping the remainder of 567 base units.
+ * 3. The dropped value is never tracked or refunded — it accrues to the
+ *    protocol invisibly, and the user's books no longer reconcile.
+ */
+
+#[cfg(test)]
+mod share_vault_tests {
+    use super::*;
+
+    /// 100 mixed deposits and redeems, each flooring a conversion. The gap
+    /// between recorded assets and what the shares represent grows with the
+    /// operation count — there is no bound a reconciler could check against.
+    #[test]
+    fn vuln_share_books_drift_without_bound() {
+        let price_scaled = 1_500; // 1.5 asset units per share
+        let mut total_assets: u64 = 0;
+        let mut total_shares: u64 = 0;
+
+        for i in 0u64..100 {
+            if i % 3 == 2 && total_shares > 10 {
+                // Redeem a tenth of the outstanding shares.
+                let shares = total_shares / 10;
+                total_shares -= shares;
+                total_assets -= assets_for_shares_vuln(shares, price_scaled);
+            } else {
+                // Deterministic "random" deposit amounts.
+                let amount = 997 + (i * 613) % 5_000;
+                total_assets += amount;
+                total_shares += shares_for_deposit_vuln(amount, price_scaled);
+            }
+        }
+
+        let drift = books_drift_vuln(total_assets, total_shares, price_scaled);
+        // Roughly one unit leaks per flooring; after 100 operations the
+        // books are off by dozens of units and still climbing.
+        assert!(
+            drift > 10,
+            "expected accumulated rounding drift, got {}",
+            drift
+        );
+    }
+}
+
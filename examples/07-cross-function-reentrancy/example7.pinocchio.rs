@@ -0,0 +1,141 @@
+#[derive(Clone, Debug, PartialEq)]
+struct Vault {
+    balance: u64,
+    rewards: u64,
+    // Per-instruction flags (the vulnerable design).
+    withdraw_locked: bool,
+    claim_locked: bool,
+    // Shared entry counter (the fixed design).
+    entries: u8,
+}
+
+// --- Vulnerable design: one lock per instruction ---
+
+fn flag_withdraw(
+    vault: &mut Vault,
+    amount: u64,
+    hook: impl FnOnce(&mut Vault) -> Result<(), &'static str>,
+) -> Result<(), &'static str> {
+    if vault.withdraw_locked {
+        return Err("re-entrancy blocked");
+    }
+    vault.withdraw_locked = true;
+
+    // CPI to the attacker's hook while the balance is still undebited. The
+    // withdraw flag is set, but claim_rewards checks a DIFFERENT flag.
+    hook(vault)?;
+
+    vault.balance = vault.balance.checked_sub(amount).ok_or("insufficient")?;
+    vault.withdraw_locked = false;
+    Ok(())
+}
+
+fn flag_claim(vault: &mut Vault) -> Result<(), &'static str> {
+    if vault.claim_locked {
+        return Err("re-entrancy blocked");
+    }
+    vault.claim_locked = true;
+
+    let payout = vault.rewards;
+    vault.balance = vault.balance.checked_sub(payout).ok_or("insufficient")?;
+    vault.rewards = 0;
+
+    vault.claim_locked = false;
+    Ok(())
+}
+
+// --- Fixed design: one counter shared by every instruction ---
+
+fn counter_withdraw(
+    vault: &mut Vault,
+    amount: u64,
+    hook: impl FnOnce(&mut Vault) -> Result<(), &'static str>,
+) -> Result<(), &'static str> {
+    if vault.entries != 0 {
+        return Err("re-entrancy blocked");
+    }
+    vault.entries += 1;
+
+    // Effects before interactions, and the shared counter stays raised for
+    // the whole call.
+    vault.balance = vault.balance.checked_sub(amount).ok_or("insufficient")?;
+    hook(vault)?;
+
+    vault.entries -= 1;
+    Ok(())
+}
+
+fn counter_claim(vault: &mut Vault) -> Result<(), &'static str> {
+    if vault.entries != 0 {
+        return Err("re-entrancy blocked");
+    }
+    vault.entries += 1;
+
+    let payout = vault.rewards;
+    vault.balance = vault.balance.checked_sub(payout).ok_or("insufficient")?;
+    vault.rewards = 0;
+
+    vault.entries -= 1;
+    Ok(())
+}
+
+#[cfg(test)]
+mod pinocchio_tests {
+    use super::*;
+
+    fn fresh_vault() -> Vault {
+        Vault {
+            balance: 1_000,
+            rewards: 400,
+            withdraw_locked: false,
+            claim_locked: false,
+            entries: 0,
+        }
+    }
+
+    #[test]
+    fn per_instruction_flags_allow_the_cross_function_drain() {
+        let mut vault = fresh_vault();
+
+        // The attacker's hook re-enters claim_rewards during withdraw's CPI.
+        // claim_locked is false, so the nested claim sails through.
+        flag_withdraw(&mut vault, 600, flag_claim).unwrap();
+
+        // 600 withdrawn + 400 rewards claimed mid-flight: fully drained,
+        // even though each instruction was individually "locked".
+        assert_eq!(vault.balance, 0);
+        assert_eq!(vault.rewards, 0);
+    }
+
+    #[test]
+    fn per_instruction_flags_do_stop_same_function_recursion() {
+        let mut vault = fresh_vault();
+
+        // withdraw→withdraw recursion is the one case the flag handles.
+        let err = flag_withdraw(&mut vault, 100, |v| flag_withdraw(v, 100, |_| Ok(()))).unwrap_err();
+        assert_eq!(err, "re-entrancy blocked");
+    }
+
+    #[test]
+    fn shared_counter_blocks_the_nested_claim() {
+        let mut vault = fresh_vault();
+
+        // The same attack against the counter design: the nested claim sees
+        // entries == 1 and is refused.
+        let err = counter_withdraw(&mut vault, 600, counter_claim).unwrap_err();
+        assert_eq!(err, "re-entrancy blocked");
+    }
+
+    #[test]
+    fn shared_counter_still_allows_sequential_calls() {
+        let mut vault = fresh_vault();
+
+        // Legitimate, non-nested usage is unaffected.
+        counter_withdraw(&mut vault, 600, |_| Ok(())).unwrap();
+        counter_claim(&mut vault).unwrap();
+
+        assert_eq!(vault.balance, 0);
+        assert_eq!(vault.rewards, 0);
+        assert_eq!(vault.entries, 0);
+    }
+}
@@ -0,0 +1,106 @@
+#![allow(unexpected_cfgs)]
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::invoke;
+
+#[account]
+pub struct Vault {
+    pub owner: Pubkey,
+    pub balance: u64,
+    pub rewards: u64,
+    // One lock PER INSTRUCTION — this is the trap. Each flag only protects
+    // its own handler from recursive entry into itself.
+    pub withdraw_locked: bool,
+    pub claim_locked: bool,
+}
+
+declare_id!("7ZubkvdFsQgpebGrrfsRqsCjFr7LpPpM5AEjT7zYfyHb");
+
+#[program]
+pub mod cross_function_reentrancy_vuln {
+    use super::*;
+
+    pub fn withdraw(ctx: Context<VaultOp>, amount: u64) -> Result<()> {
+        let vault_key = ctx.accounts.vault.key();
+        let hook_info = ctx.accounts.hook_program.to_account_info();
+        let vault_info = ctx.accounts.vault.to_account_info();
+        let vault = &mut ctx.accounts.vault;
+
+        // --- THE VULNERABILITY ---
+        // This guard looks like the single-instruction reentrancy fix, and
+        // against withdraw→withdraw recursion it even works. But it is a
+        // PER-INSTRUCTION flag: while we sit inside the CPI below,
+        // `claim_locked` is still false, so the attacker's hook happily
+        // re-enters `claim_rewards` and drains through the second path.
+        require!(!vault.withdraw_locked, CustomError::Reentrancy);
+        vault.withdraw_locked = true;
+
+        // External call BEFORE the balance is debited — the attacker's hook
+        // runs against a vault that still carries the full balance and all
+        // unclaimed rewards.
+        invoke(
+            &anchor_lang::solana_program::instruction::Instruction {
+                program_id: ctx.accounts.hook_program.key(),
+                accounts: vec![
+                    anchor_lang::solana_program::instruction::AccountMeta::new(vault_key, false),
+                ],
+                data: vec![],
+            },
+            &[vault_info, hook_info],
+        )
+        .ok();
+
+        vault.balance = vault
+            .balance
+            .checked_sub(amount)
+            .ok_or(CustomError::InsufficientFunds)?;
+
+        vault.withdraw_locked = false;
+        Ok(())
+    }
+
+    pub fn claim_rewards(ctx: Context<VaultOp>) -> Result<()> {
+        let vault = &mut ctx.accounts.vault;
+
+        // Same pattern, same blind spot: this flag knows nothing about an
+        // in-flight `withdraw`, so a nested call lands here unopposed.
+        require!(!vault.claim_locked, CustomError::Reentrancy);
+        vault.claim_locked = true;
+
+        // Rewards are paid out of the shared balance.
+        let payout = vault.rewards;
+        vault.balance = vault
+            .balance
+            .checked_sub(payout)
+            .ok_or(CustomError::InsufficientFunds)?;
+        vault.rewards = 0;
+
+        vault.claim_locked = false;
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct VaultOp<'info> {
+    #[account(mut, has_one = owner)]
+    pub vault: Account<'info, Vault>,
+    pub owner: Signer<'info>,
+    /// CHECK: the hook program called during withdraw
+    pub hook_program: AccountInfo<'info>,
+}
+
+#[error_code]
+pub enum CustomError {
+    #[msg("re-entrancy blocked")]
+    Reentrancy,
+    #[msg("insufficient funds")]
+    InsufficientFunds,
+}
+
+/**
+ * SUMMARY OF THE BUG:
+ * 1. Both instructions mutate the SAME vault but each carries its OWN lock.
+ * 2. During withdraw's CPI, claim_locked is still false.
+ * 3. The attacker's hook re-enters claim_rewards, draining rewards out of a
+ *    balance that withdraw has not debited yet.
+ * 4. Per-instruction locks only stop f→f recursion; the attack goes f→g.
+ */
@@ -0,0 +1,62 @@
+#![allow(unexpected_cfgs)]
+use anchor_lang::prelude::*;
+
+#[account]
+pub struct Balance {
+    pub owner: Pubkey,
+    pub amount: u64,
+}
+
+declare_id!("ArDh2dHxHSNjHte8uaYTeEMG4U4edAPu9aKhmX9Y44s9");
+
+#[program]
+pub mod self_transfer_fix {
+    use super::*;
+
+    pub fn transfer(ctx: Context<TransferSafe>, amount: u64) -> Result<()> {
+        // --- THE FIX: HANDLE from == to EXPLICITLY ---
+        // A transfer to oneself is semantically a no-op, so make it one.
+        // Returning early means the two account wrappers are never both
+        // mutated, which kills the aliasing bug in every refactoring: no
+        // double-applied credit, no double-borrow, no dependence on
+        // serialization order.
+        //
+        // (Still validate the amount first so a self-"transfer" of more
+        // than the balance fails the same way a real one would.)
+        let from = &ctx.accounts.from;
+        require!(amount <= from.amount, CustomError::InsufficientFunds);
+
+        if ctx.accounts.from.key() == ctx.accounts.to.key() {
+            msg!("self-transfer: no-op");
+            return Ok(());
+        }
+
+        let from = &mut ctx.accounts.from;
+        from.amount -= amount; // bounded by the require above
+
+        let to = &mut ctx.accounts.to;
+        to.amount = to
+            .amount
+            .checked_add(amount)
+            .ok_or(CustomError::BalanceOverflow)?;
+
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct TransferSafe<'info> {
+    #[account(mut, has_one = owner)]
+    pub from: Account<'info, Balance>,
+    #[account(mut)]
+    pub to: Account<'info, Balance>,
+    pub owner: Signer<'info>,
+}
+
+#[error_code]
+pub enum CustomError {
+    #[msg("insufficient funds")]
+    InsufficientFunds,
+    #[msg("balance overflow")]
+    BalanceOverflow,
+}
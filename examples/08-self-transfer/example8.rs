@@ -0,0 +1,78 @@
+#![allow(unexpected_cfgs)]
+use anchor_lang::prelude::*;
+
+#[account]
+pub struct Balance {
+    pub owner: Pubkey,
+    pub amount: u64,
+}
+
+declare_id!("FeyRqrKNAt3k6NAdNQ7p7bswtYcXXqFwApbeXZzQAjoF");
+
+#[program]
+pub mod self_transfer_vuln {
+    use super::*;
+
+    pub fn transfer(ctx: Context<TransferVuln>, amount: u64) -> Result<()> {
+        // --- THE VULNERABILITY ---
+        // Nothing stops the caller from passing the SAME account as both
+        // `from` and `to`. Anchor then deserializes two INDEPENDENT copies
+        // of the same underlying data:
+        //
+        //   from.amount = balance - amount   (copy #1)
+        //   to.amount   = balance + amount   (copy #2, also started at balance)
+        //
+        // On exit Anchor serializes `from` first and `to` second — last
+        // write wins, so the account ends at balance + amount. A
+        // self-transfer MINTS the transferred amount out of thin air.
+        //
+        // And the bug is unstable under refactoring: a version that borrows
+        // both accounts' data mutably at once (instead of working on
+        // copies) hits a double-borrow on the aliased account and aborts.
+        // Either way, `from == to` was never handled — it just happened to
+        // "work" until it didn't.
+        let from = &mut ctx.accounts.from;
+        let to = &mut ctx.accounts.to;
+
+        from.amount = from
+            .amount
+            .checked_sub(amount)
+            .ok_or(CustomError::InsufficientFunds)?;
+        to.amount = to
+            .amount
+            .checked_add(amount)
+            .ok_or(CustomError::BalanceOverflow)?;
+
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct TransferVuln<'info> {
+    #[account(mut, has_one = owner)]
+    pub from: Account<'info, Balance>,
+    /// No constraint relates `to` to `from` — the same account can appear
+    /// in both slots.
+    #[account(mut)]
+    pub to: Account<'info, Balance>,
+    pub owner: Signer<'info>,
+}
+
+#[error_code]
+pub enum CustomError {
+    #[msg("insufficient funds")]
+    InsufficientFunds,
+    #[msg("balance overflow")]
+    BalanceOverflow,
+}
+
+/**
+ * SUMMARY OF THE BUG:
+ * 1. The caller passes the same Balance account as `from` and `to`.
+ * 2. Both wrappers start from the same deserialized balance; the debit and
+ *    the credit are applied to separate copies.
+ * 3. Serialization order makes the credit win: the balance grows by
+ *    `amount` with no funds moving anywhere.
+ * 4. Duplicate mutable accounts must be either rejected or handled
+ *    explicitly — never left to serialization luck.
+ */
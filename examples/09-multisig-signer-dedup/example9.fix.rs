@@ -0,0 +1,63 @@
+#![allow(unexpected_cfgs)]
+use anchor_lang::prelude::*;
+use std::collections::BTreeSet;
+
+#[account]
+pub struct MultiSigConfig {
+    pub signers: Vec<Pubkey>, // the n authorized keys
+    pub threshold: u8,        // m signatures required
+    pub executed: u64,        // actions executed so far
+}
+
+declare_id!("CGLEKhDEFFrhPmzTKjejtTDq6Y3ToSkPiGW66dE7zvzd");
+
+#[program]
+pub mod multisig_fix {
+    use super::*;
+
+    pub fn execute<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ExecuteSafe<'info>>,
+    ) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+
+        // --- THE FIX: COUNT DISTINCT SIGNERS ---
+        // Collect the provided signers into a set as we validate them. A
+        // repeated key fails the insert and is rejected outright — loudly,
+        // rather than silently counted once, so a client buggy enough to
+        // duplicate signers hears about it.
+        let mut seen = BTreeSet::new();
+        let mut valid_count = 0u8;
+        for signer in ctx.remaining_accounts {
+            if !signer.is_signer || !config.signers.contains(signer.key) {
+                continue;
+            }
+            require!(seen.insert(*signer.key), CustomError::DuplicateSigner);
+            valid_count += 1;
+        }
+
+        require!(
+            valid_count >= config.threshold,
+            CustomError::InsufficientSigners
+        );
+
+        config.executed += 1;
+        msg!("action executed with {} distinct signatures", valid_count);
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct ExecuteSafe<'info> {
+    #[account(mut)]
+    pub config: Account<'info, MultiSigConfig>,
+    /// The transaction fee payer; co-signers come via remaining accounts.
+    pub payer: Signer<'info>,
+}
+
+#[error_code]
+pub enum CustomError {
+    #[msg("not enough valid signatures for the threshold")]
+    InsufficientSigners,
+    #[msg("the same signer was provided more than once")]
+    DuplicateSigner,
+}
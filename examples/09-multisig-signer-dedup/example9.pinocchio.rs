@@ -0,0 +1,86 @@
+use std::collections::BTreeSet;
+
+type Pubkey = [u8; 32];
+
+struct MultiSigConfig {
+    signers: Vec<Pubkey>,
+    threshold: u8,
+}
+
+// Mirrors the vulnerable quorum check: counts signatures, not signers.
+fn vuln_validate(config: &MultiSigConfig, provided: &[Pubkey]) -> Result<(), &'static str> {
+    let mut valid_count = 0u8;
+    for signer in provided {
+        if config.signers.contains(signer) {
+            valid_count += 1;
+        }
+    }
+    if valid_count < config.threshold {
+        return Err("insufficient signers");
+    }
+    Ok(())
+}
+
+// Mirrors the fix: a BTreeSet rejects any repeated signer before counting.
+fn safe_validate(config: &MultiSigConfig, provided: &[Pubkey]) -> Result<(), &'static str> {
+    let mut seen = BTreeSet::new();
+    let mut valid_count = 0u8;
+    for signer in provided {
+        if !config.signers.contains(signer) {
+            continue;
+        }
+        if !seen.insert(*signer) {
+            return Err("duplicate signer");
+        }
+        valid_count += 1;
+    }
+    if valid_count < config.threshold {
+        return Err("insufficient signers");
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod pinocchio_tests {
+    use super::*;
+
+    fn key(byte: u8) -> Pubkey {
+        [byte; 32]
+    }
+
+    fn two_of_three() -> MultiSigConfig {
+        MultiSigConfig {
+            signers: vec![key(1), key(2), key(3)],
+            threshold: 2,
+        }
+    }
+
+    #[test]
+    fn one_key_passed_twice_defeats_the_vulnerable_quorum() {
+        let config = two_of_three();
+
+        // A single compromised key reaches the 2-of-3 threshold by simply
+        // appearing twice in the signer list.
+        vuln_validate(&config, &[key(1), key(1)]).unwrap();
+    }
+
+    #[test]
+    fn fix_rejects_the_duplicated_signer() {
+        let config = two_of_three();
+
+        let err = safe_validate(&config, &[key(1), key(1)]).unwrap_err();
+        assert_eq!(err, "duplicate signer");
+    }
+
+    #[test]
+    fn two_distinct_signers_satisfy_both_versions() {
+        let config = two_of_three();
+
+        vuln_validate(&config, &[key(1), key(3)]).unwrap();
+        safe_validate(&config, &[key(1), key(3)]).unwrap();
+
+        // One distinct signer is short of the threshold either way.
+        assert!(vuln_validate(&config, &[key(2)]).is_err());
+        assert!(safe_validate(&config, &[key(2)]).is_err());
+    }
+}
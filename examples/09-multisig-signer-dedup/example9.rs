@@ -0,0 +1,67 @@
+#![allow(unexpected_cfgs)]
+use anchor_lang::prelude::*;
+
+#[account]
+pub struct MultiSigConfig {
+    pub signers: Vec<Pubkey>, // the n authorized keys
+    pub threshold: u8,        // m signatures required
+    pub executed: u64,        // actions executed so far
+}
+
+declare_id!("Gpw3dBYCotdbRwtSt4aVzdmRUhdrdai9kKKz6rxqssKo");
+
+#[program]
+pub mod multisig_vuln {
+    use super::*;
+
+    /// Executes a guarded action once `threshold` of the configured signers
+    /// have signed. The co-signers arrive as remaining accounts.
+    pub fn execute<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ExecuteVuln<'info>>,
+    ) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+
+        // --- THE VULNERABILITY ---
+        // Every remaining account that (a) actually signed and (b) appears
+        // in the authorized set bumps the counter. But NOTHING stops the
+        // caller from listing the SAME signer account several times: one
+        // compromised key, passed m times, "is" an m-of-n quorum.
+        let mut valid_count = 0u8;
+        for signer in ctx.remaining_accounts {
+            if signer.is_signer && config.signers.contains(signer.key) {
+                valid_count += 1;
+            }
+        }
+
+        require!(
+            valid_count >= config.threshold,
+            CustomError::InsufficientSigners
+        );
+
+        config.executed += 1;
+        msg!("action executed with {} signatures", valid_count);
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct ExecuteVuln<'info> {
+    #[account(mut)]
+    pub config: Account<'info, MultiSigConfig>,
+    /// The transaction fee payer; co-signers come via remaining accounts.
+    pub payer: Signer<'info>,
+}
+
+#[error_code]
+pub enum CustomError {
+    #[msg("not enough valid signatures for the threshold")]
+    InsufficientSigners,
+}
+
+/**
+ * SUMMARY OF THE BUG:
+ * 1. The quorum check counts signatures, not DISTINCT signers.
+ * 2. Passing the same authorized signer account twice yields two counts
+ *    from one key.
+ * 3. A single compromised key defeats the entire m-of-n design.
+ */
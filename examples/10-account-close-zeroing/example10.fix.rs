@@ -0,0 +1,47 @@
+#![allow(unexpected_cfgs)]
+use anchor_lang::prelude::*;
+
+#[account]
+pub struct UserAccount {
+    pub owner: Pubkey,
+    pub balance: u64,
+}
+
+declare_id!("7y1i1wE3suzTu4ZaRyzBwqLWt6tx8UQxtE98F3r7DnMd");
+
+#[program]
+pub mod account_close_fix {
+    use super::*;
+
+    pub fn close(_ctx: Context<CloseSafe>) -> Result<()> {
+        // --- THE FIX ---
+        // No hand-rolled lamport plumbing: Anchor's `close = recipient`
+        // constraint does the full teardown. Besides moving the lamports it
+        // zeroes the account data, writes the CLOSED sentinel discriminator
+        // and hands the account back to the system program. Refunding rent
+        // to the address no longer revives anything — deserialization of
+        // the old type fails because the discriminator is gone.
+        msg!("Account closed; data and discriminator zeroed");
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct CloseSafe<'info> {
+    #[account(mut, has_one = owner, close = recipient)]
+    pub user_account: Account<'info, UserAccount>,
+    pub owner: Signer<'info>,
+    /// CHECK: rent refund target
+    #[account(mut)]
+    pub recipient: AccountInfo<'info>,
+}
+
+/**
+ * WHY THIS WORKS:
+ * 1. `close = recipient` drains the lamports AND wipes the data, so there
+ *    is no window where a rent refund resurrects valid state.
+ * 2. The sentinel discriminator makes any later `Account<UserAccount>`
+ *    deserialization fail instead of silently accepting a zombie.
+ * 3. One declarative constraint replaces three easy-to-get-wrong manual
+ *    steps — prefer it over hand-written close logic every time.
+ */
@@ -0,0 +1,105 @@
+// Models an account slated for closing as (lamports, data). The vulnerable
+// close only zeroes the lamports; the fixed close also wipes the data and
+// stamps the closed sentinel over the discriminator.
+
+const DISCRIMINATOR_LEN: usize = 8;
+const CLOSED_DISCRIMINATOR: [u8; DISCRIMINATOR_LEN] = [0xff; DISCRIMINATOR_LEN];
+const USER_ACCOUNT_DISCRIMINATOR: [u8; DISCRIMINATOR_LEN] = [211, 33, 136, 16, 186, 110, 242, 127];
+
+struct ClosableAccount {
+    lamports: u64,
+    data: Vec<u8>,
+}
+
+fn live_user_account(balance: u64) -> ClosableAccount {
+    let mut data = USER_ACCOUNT_DISCRIMINATOR.to_vec();
+    data.extend_from_slice(&[7u8; 32]); // owner
+    data.extend_from_slice(&balance.to_le_bytes());
+    ClosableAccount {
+        lamports: 2_039_280,
+        data,
+    }
+}
+
+// Mirrors the vulnerable close: drains lamports, never touches the data.
+fn vuln_close(account: &mut ClosableAccount, recipient_lamports: &mut u64) {
+    *recipient_lamports += account.lamports;
+    account.lamports = 0;
+}
+
+// Mirrors Anchor's `close` constraint: drains lamports, zeroes the data and
+// writes the closed sentinel over the discriminator.
+fn safe_close(account: &mut ClosableAccount, recipient_lamports: &mut u64) {
+    *recipient_lamports += account.lamports;
+    account.lamports = 0;
+    for byte in account.data.iter_mut() {
+        *byte = 0;
+    }
+    account.data[..DISCRIMINATOR_LEN].copy_from_slice(&CLOSED_DISCRIMINATOR);
+}
+
+// Stand-in for a program's deserialization gate: accepts the account only
+// if the discriminator still identifies it as a live UserAccount.
+fn try_read_balance(account: &ClosableAccount) -> Option<u64> {
+    if account.data.len() < DISCRIMINATOR_LEN + 32 + 8 {
+        return None;
+    }
+    if account.data[..DISCRIMINATOR_LEN] != USER_ACCOUNT_DISCRIMINATOR {
+        return None;
+    }
+    let mut balance = [0u8; 8];
+    balance.copy_from_slice(&account.data[DISCRIMINATOR_LEN + 32..DISCRIMINATOR_LEN + 40]);
+    Some(u64::from_le_bytes(balance))
+}
+
+#[cfg(test)]
+mod pinocchio_tests {
+    use super::*;
+
+    #[test]
+    fn vulnerable_close_leaves_revivable_data() {
+        let mut account = live_user_account(500);
+        let mut recipient = 0u64;
+
+        vuln_close(&mut account, &mut recipient);
+        assert_eq!(account.lamports, 0);
+        assert_eq!(recipient, 2_039_280);
+
+        // An attacker refunds rent in the same transaction...
+        account.lamports = 890_880;
+
+        // ...and the zombie deserializes exactly like live state.
+        assert_eq!(try_read_balance(&account), Some(500));
+    }
+
+    #[test]
+    fn safe_close_zeroes_data_so_revival_fails() {
+        let mut account = live_user_account(500);
+        let mut recipient = 0u64;
+
+        safe_close(&mut account, &mut recipient);
+        assert_eq!(account.lamports, 0);
+        assert_eq!(recipient, 2_039_280);
+
+        // The refund trick no longer works: the discriminator is gone, so
+        // the deserialization gate refuses the account.
+        account.lamports = 890_880;
+        assert_eq!(try_read_balance(&account), None);
+
+        // Everything past the sentinel is zeroed too.
+        assert!(account.data[DISCRIMINATOR_LEN..].iter().all(|&b| b == 0));
+    }
+
+    #[test]
+    fn both_closes_move_the_same_lamports() {
+        let mut vulnerable = live_user_account(1);
+        let mut fixed = live_user_account(1);
+        let (mut to_a, mut to_b) = (0u64, 0u64);
+
+        vuln_close(&mut vulnerable, &mut to_a);
+        safe_close(&mut fixed, &mut to_b);
+
+        // The fix changes what happens to the data, not the rent refund.
+        assert_eq!(to_a, to_b);
+    }
+}
@@ -0,0 +1,66 @@
+#![allow(unexpected_cfgs)]
+use anchor_lang::prelude::*;
+
+#[account]
+pub struct UserAccount {
+    pub owner: Pubkey,
+    pub balance: u64,
+}
+
+declare_id!("4u3cbYbn2DjJxnrykC3cpzu1M3jC3hMjzX2MLihYyBko");
+
+#[program]
+pub mod account_close_vuln {
+    use super::*;
+
+    pub fn close(ctx: Context<CloseVuln>) -> Result<()> {
+        let account_info = ctx.accounts.user_account.to_account_info();
+        let recipient_info = ctx.accounts.recipient.to_account_info();
+
+        // --- THE VULNERABILITY ---
+        // "Closing" by draining lamports only. The data — discriminator,
+        // owner, balance — is left fully intact.
+        //
+        // The runtime garbage-collects zero-lamport accounts at the END of
+        // the transaction. Until then (and worse, if anyone refunds rent to
+        // this address in the SAME transaction), the account still exists
+        // with perfectly valid, deserializable contents. An attacker who
+        // sends a few lamports back "revives" the account: every program
+        // that checks only the discriminator will happily accept this
+        // zombie as live state.
+        let drained = account_info.lamports();
+        **recipient_info.lamports.borrow_mut() = recipient_info
+            .lamports()
+            .checked_add(drained)
+            .ok_or(CustomError::Overflow)?;
+        **account_info.lamports.borrow_mut() = 0;
+
+        msg!("Account drained of {} lamports (data left intact!)", drained);
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct CloseVuln<'info> {
+    #[account(mut, has_one = owner)]
+    pub user_account: Account<'info, UserAccount>,
+    pub owner: Signer<'info>,
+    /// CHECK: rent refund target
+    #[account(mut)]
+    pub recipient: AccountInfo<'info>,
+}
+
+#[error_code]
+pub enum CustomError {
+    #[msg("lamport arithmetic overflow")]
+    Overflow,
+}
+
+/**
+ * SUMMARY OF THE BUG:
+ * 1. Close = zero the lamports. Nothing zeroes the DATA.
+ * 2. Refunding rent to the address before the end of the transaction
+ *    revives the account with its old discriminator and fields intact.
+ * 3. Any instruction that trusts the discriminator now operates on state
+ *    the protocol believed was destroyed.
+ */
@@ -0,0 +1,103 @@
+#![allow(unexpected_cfgs)]
+use anchor_lang::prelude::*;
+
+#[account]
+pub struct Balance {
+    pub owner: Pubkey,
+    pub amount: u64,
+}
+
+declare_id!("CjTs1grc8jjaypGcc3wBg5ymbj9eJCYYHNH7EC5K9b9f");
+
+#[program]
+pub mod batch_credit_fix {
+    use super::*;
+
+    pub fn batch_credit<'info>(
+        ctx: Context<'_, '_, 'info, 'info, BatchCreditSafe<'info>>,
+        amounts: Vec<u64>,
+    ) -> Result<()> {
+        // --- THE FIX ---
+        // Validate both batch preconditions up front: the batch is small
+        // enough to finish inside the compute budget, and the positional
+        // invariant holds — one amount per account, exactly. A violation is
+        // a malformed call, and the right response is a clean error before
+        // ANY balance is touched — not a panic halfway through, not a
+        // silently truncated batch, and not a CU-exhaustion abort mid-loop.
+        validate_batch(ctx.remaining_accounts.len(), amounts.len())?;
+
+        // `zip` is now guaranteed lossless; iterating this way also makes
+        // it impossible to reintroduce an indexing panic.
+        for (account, amount) in ctx.remaining_accounts.iter().zip(amounts.iter()) {
+            let mut data = account.try_borrow_mut_data()?;
+            let mut balance = Balance::try_deserialize(&mut &data[..])?;
+
+            balance.amount = balance.amount.saturating_add(*amount);
+
+            let mut cursor: &mut [u8] = &mut data;
+            balance.try_serialize(&mut cursor)?;
+            msg!("credited {} to account {}", amount, account.key());
+        }
+        Ok(())
+    }
+}
+
+// Compute-budget guard: each iteration pays for a deserialize, a serialize
+// and a log, so past this size a batch starts flirting with the
+// per-transaction CU limit. An attacker-sized batch that dies of compute
+// exhaustion mid-loop is a DoS on everyone who co-scheduled with it; the
+// vuln accepts any length and finds out at runtime.
+pub const MAX_BATCH: usize = 16;
+
+/// Both batch preconditions in one place, checked before the loop so the
+/// batch stays atomic: bounded size first, then matched lengths.
+pub fn validate_batch(accounts_len: usize, amounts_len: usize) -> Result<()> {
+    require!(amounts_len <= MAX_BATCH, CustomError::BatchTooLarge);
+    require_eq!(accounts_len, amounts_len, CustomError::LengthMismatch);
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct BatchCreditSafe<'info> {
+    /// Whoever is funding the batch; targets come via remaining accounts.
+    pub payer: Signer<'info>,
+}
+
+#[error_code]
+pub enum CustomError {
+    #[msg("the number of amounts must equal the number of target accounts")]
+    LengthMismatch,
+    #[msg("the batch exceeds the maximum size the compute budget allows")]
+    BatchTooLarge,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// `MAX_BATCH + 1` items must be rejected as oversized before the
+    /// length comparison even runs — a correctly paired but huge batch is
+    /// still a compute-budget DoS.
+    #[test]
+    fn oversized_batch_is_rejected_up_front() {
+        let err = validate_batch(MAX_BATCH + 1, MAX_BATCH + 1).unwrap_err();
+        assert!(format!("{}", err).contains("compute budget"));
+
+        // At the limit exactly, with matched lengths, the batch is fine.
+        validate_batch(MAX_BATCH, MAX_BATCH).unwrap();
+
+        // The original mismatch check still guards smaller batches.
+        let err = validate_batch(3, 2).unwrap_err();
+        assert!(format!("{}", err).contains("must equal"));
+    }
+}
+
+/**
+ * WHY THIS WORKS:
+ * 1. The length check turns both failure modes (panic, silent drop) into
+ *    one explicit, testable error.
+ * 2. Checking BEFORE the loop keeps the batch atomic: either every pair
+ *    is processed or none is.
+ * 3. zip over paired iterators beats indexing — the compiler enforces
+ *    what the require_eq! established.
+ */
@@ -0,0 +1,74 @@
+// Models the batch-credit instruction as pure functions over a slice of
+// balances (the remaining accounts) and a slice of amounts (the instruction
+// data). The pairing is positional, which is exactly where it breaks.
+
+// Mirrors the vulnerable loop: iterates the accounts and indexes into the
+// amounts, with no length check anywhere.
+fn vuln_batch_credit(balances: &mut [u64], amounts: &[u64]) {
+    for (i, balance) in balances.iter_mut().enumerate() {
+        *balance = balance.saturating_add(amounts[i]);
+    }
+}
+
+// Mirrors the fix: lengths must match before anything is credited.
+fn safe_batch_credit(balances: &mut [u64], amounts: &[u64]) -> Result<(), &'static str> {
+    if balances.len() != amounts.len() {
+        return Err("length mismatch");
+    }
+    for (balance, amount) in balances.iter_mut().zip(amounts.iter()) {
+        *balance = balance.saturating_add(*amount);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod pinocchio_tests {
+    use super::*;
+
+    #[test]
+    #[should_panic(expected = "index out of bounds")]
+    fn too_few_amounts_panic_the_vulnerable_batch() {
+        let mut balances = vec![100, 200, 300];
+
+        // Three accounts, two amounts: amounts[2] doesn't exist and the
+        // whole batch aborts mid-flight.
+        vuln_batch_credit(&mut balances, &[10, 20]);
+    }
+
+    #[test]
+    fn too_few_accounts_silently_drop_a_payment() {
+        let mut balances = vec![100, 200];
+
+        // Three amounts, two accounts: the loop ends at the accounts and
+        // the 30-lamport payment vanishes without an error.
+        vuln_batch_credit(&mut balances, &[10, 20, 30]);
+        assert_eq!(balances, vec![110, 220]);
+        // Nothing anywhere records that the third payment was dropped.
+    }
+
+    #[test]
+    fn fix_rejects_both_mismatch_directions_before_crediting() {
+        let mut balances = vec![100, 200, 300];
+
+        let err = safe_batch_credit(&mut balances, &[10, 20]).unwrap_err();
+        assert_eq!(err, "length mismatch");
+        let err = safe_batch_credit(&mut balances, &[10, 20, 30, 40]).unwrap_err();
+        assert_eq!(err, "length mismatch");
+
+        // Atomicity: the failed calls touched nothing.
+        assert_eq!(balances, vec![100, 200, 300]);
+    }
+
+    #[test]
+    fn matched_lengths_credit_every_account() {
+        let mut vulnerable = vec![100, 200, 300];
+        let mut fixed = vec![100, 200, 300];
+
+        vuln_batch_credit(&mut vulnerable, &[10, 20, 30]);
+        safe_batch_credit(&mut fixed, &[10, 20, 30]).unwrap();
+
+        // On well-formed input the two versions agree.
+        assert_eq!(vulnerable, vec![110, 220, 330]);
+        assert_eq!(fixed, vulnerable);
+    }
+}
@@ -0,0 +1,61 @@
+#![allow(unexpected_cfgs)]
+use anchor_lang::prelude::*;
+
+#[account]
+pub struct Balance {
+    pub owner: Pubkey,
+    pub amount: u64,
+}
+
+declare_id!("3n8ADt5wNvwYrSA1GazcqjrD9xJMjGKp2jopjDaCHpBD");
+
+#[program]
+pub mod batch_credit_vuln {
+    use super::*;
+
+    /// Credits a batch of balance accounts in one call: the accounts come
+    /// in via `remaining_accounts`, the amounts via the instruction data,
+    /// and the pairing is purely POSITIONAL — account[i] gets amounts[i].
+    pub fn batch_credit<'info>(
+        ctx: Context<'_, '_, 'info, 'info, BatchCreditVuln<'info>>,
+        amounts: Vec<u64>,
+    ) -> Result<()> {
+        // --- THE VULNERABILITY ---
+        // Nothing checks that the two lists are the same length.
+        //
+        //  * More accounts than amounts: `amounts[i]` indexes out of
+        //    bounds and the program PANICS mid-batch — a griefing vector,
+        //    since any caller can abort the whole batch.
+        //  * More amounts than accounts: the loop ends early and the
+        //    trailing amounts are SILENTLY DROPPED. The caller believes
+        //    every payment in the list went out; some never did.
+        //  * And nothing bounds the batch size at all: a large enough
+        //    batch burns the whole compute budget and aborts mid-loop —
+        //    see MAX_BATCH in the fix.
+        for (i, account) in ctx.remaining_accounts.iter().enumerate() {
+            let mut data = account.try_borrow_mut_data()?;
+            let mut balance = Balance::try_deserialize(&mut &data[..])?;
+
+            balance.amount = balance.amount.saturating_add(amounts[i]);
+
+            let mut cursor: &mut [u8] = &mut data;
+            balance.try_serialize(&mut cursor)?;
+            msg!("credited {} to account {}", amounts[i], account.key());
+        }
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct BatchCreditVuln<'info> {
+    /// Whoever is funding the batch; targets come via remaining accounts.
+    pub payer: Signer<'info>,
+}
+
+/**
+ * SUMMARY OF THE BUG:
+ * 1. Positionally paired lists are only meaningful when their lengths
+ *    match — and the transaction format lets the caller send any lengths.
+ * 2. Too few amounts: out-of-bounds panic, the batch is griefable.
+ * 3. Too few accounts: payments silently vanish off the end of the list.
+ */
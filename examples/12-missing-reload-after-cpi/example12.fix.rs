@@ -0,0 +1,69 @@
+#![allow(unexpected_cfgs)]
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::solana_program::program::invoke;
+
+#[account]
+pub struct Vault {
+    pub owner: Pubkey,
+    pub balance: u64,
+}
+
+declare_id!("Bzd11RWqASywUQgLXrdxVXsTdBPt8yfngmWnj8xD5s39");
+
+#[program]
+pub mod stale_reload_fix {
+    use super::*;
+
+    pub fn collect_fee(ctx: Context<CollectFeeSafe>, fee: u64) -> Result<()> {
+        invoke(
+            &Instruction {
+                program_id: ctx.accounts.rewards_program.key(),
+                accounts: vec![AccountMeta::new(ctx.accounts.vault.key(), false)],
+                data: vec![],
+            },
+            &[ctx.accounts.vault.to_account_info()],
+        )?;
+
+        // --- THE FIX: RELOAD AFTER THE CPI ---
+        // `reload()` re-deserializes the account from its (possibly
+        // CPI-mutated) bytes, replacing the stale entry-time snapshot.
+        // Every read below now sees the rewards credit, so deriving the
+        // new balance can't lose it.
+        ctx.accounts.vault.reload()?;
+
+        let vault = &mut ctx.accounts.vault;
+        vault.balance = vault
+            .balance
+            .checked_sub(fee)
+            .ok_or(CustomError::InsufficientFunds)?;
+
+        msg!("fee collected; balance now {}", vault.balance);
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct CollectFeeSafe<'info> {
+    #[account(mut, has_one = owner)]
+    pub vault: Account<'info, Vault>,
+    pub owner: Signer<'info>,
+    /// CHECK: the rewards program invoked via CPI
+    pub rewards_program: AccountInfo<'info>,
+}
+
+#[error_code]
+pub enum CustomError {
+    #[msg("the fee exceeds the vault balance")]
+    InsufficientFunds,
+}
+
+/**
+ * WHY THIS WORKS:
+ * 1. reload() refreshes the deserialized view from the account bytes, so
+ *    post-CPI reads see what the CPI wrote.
+ * 2. The rule of thumb: after any CPI that may touch an account you also
+ *    hold as Account<T>, reload it before reading or deriving from it.
+ * 3. If the handler doesn't need the pre-CPI value at all, read AFTER the
+ *    CPI instead — the snapshot you never take can't go stale.
+ */
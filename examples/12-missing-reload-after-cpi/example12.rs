@@ -0,0 +1,72 @@
+#![allow(unexpected_cfgs)]
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::solana_program::program::invoke;
+
+#[account]
+pub struct Vault {
+    pub owner: Pubkey,
+    pub balance: u64,
+}
+
+declare_id!("6XooHy1WDYQGPm5jgqVXp8CJXDxF2CySVRkJavEmwytb");
+
+#[program]
+pub mod stale_reload_vuln {
+    use super::*;
+
+    /// Collects a fee from the vault, letting a rewards program credit the
+    /// vault via CPI first.
+    pub fn collect_fee(ctx: Context<CollectFeeVuln>, fee: u64) -> Result<()> {
+        // --- THE VULNERABILITY: STALE LOCAL STATE ---
+        // `Account<Vault>` deserialized the account ONCE, at instruction
+        // entry. This local copy is a snapshot, not a live view.
+        let balance_before = ctx.accounts.vault.balance;
+
+        // The CPI hands control to the rewards program, which CREDITS the
+        // vault account — the bytes on chain change underneath us.
+        invoke(
+            &Instruction {
+                program_id: ctx.accounts.rewards_program.key(),
+                accounts: vec![AccountMeta::new(ctx.accounts.vault.key(), false)],
+                data: vec![],
+            },
+            &[ctx.accounts.vault.to_account_info()],
+        )?;
+
+        // Anchor writes `ctx.accounts.vault` back when the handler returns.
+        // Deriving the new balance from the PRE-CPI snapshot discards the
+        // credit the rewards program just applied: a classic lost update.
+        ctx.accounts.vault.balance = balance_before
+            .checked_sub(fee)
+            .ok_or(CustomError::InsufficientFunds)?;
+
+        msg!("fee collected; balance now {}", ctx.accounts.vault.balance);
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct CollectFeeVuln<'info> {
+    #[account(mut, has_one = owner)]
+    pub vault: Account<'info, Vault>,
+    pub owner: Signer<'info>,
+    /// CHECK: the rewards program invoked via CPI
+    pub rewards_program: AccountInfo<'info>,
+}
+
+#[error_code]
+pub enum CustomError {
+    #[msg("the fee exceeds the vault balance")]
+    InsufficientFunds,
+}
+
+/**
+ * SUMMARY OF THE BUG:
+ * 1. An Account<T> is deserialized once at entry; a CPI can change the
+ *    underlying bytes while the handler holds the old snapshot.
+ * 2. Writing state derived from the pre-CPI snapshot silently discards
+ *    whatever the CPI did — here, the rewards credit simply vanishes.
+ * 3. Nothing fails. The lost update only shows up when someone audits
+ *    balances, long after the transaction succeeded.
+ */
@@ -0,0 +1,74 @@
+#![allow(unexpected_cfgs)]
+use anchor_lang::prelude::*;
+
+#[account]
+pub struct Escrow {
+    pub depositor: Pubkey, // who funded the escrow
+    pub recipient: Pubkey, // who the funds are destined for
+    pub arbiter: Pubkey,   // the ONLY party allowed to release
+    pub amount: u64,
+    pub released: bool,
+}
+
+declare_id!("BjdnxAw8aWcBCnURZpWargfEDc6bwxZDo2yrioi2jiCk");
+
+#[program]
+pub mod escrow_fix {
+    use super::*;
+
+    pub fn release(ctx: Context<ReleaseSafe>) -> Result<()> {
+        let escrow = &mut ctx.accounts.escrow;
+        require!(!escrow.released, CustomError::AlreadyReleased);
+
+        // By the time we get here, the `has_one = arbiter` constraint has
+        // already proven the signing account IS the arbiter recorded in
+        // the escrow. Neither the depositor nor the recipient can release.
+        escrow.released = true;
+
+        **ctx.accounts.recipient.lamports.borrow_mut() = ctx
+            .accounts
+            .recipient
+            .lamports()
+            .checked_add(escrow.amount)
+            .ok_or(CustomError::Overflow)?;
+
+        msg!("escrow released by arbiter {}", ctx.accounts.arbiter.key());
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct ReleaseSafe<'info> {
+    // --- THE FIX ---
+    // 'has_one = arbiter' ties the signing account below to the arbiter
+    // key stored INSIDE the escrow at creation. Identity (the stored key)
+    // and signature (the Signer type) are checked together; both must
+    // hold or validation fails before the handler runs.
+    #[account(mut, has_one = recipient, has_one = arbiter @ CustomError::NotTheArbiter)]
+    pub escrow: Account<'info, Escrow>,
+    /// CHECK: lamport destination recorded in the escrow
+    #[account(mut)]
+    pub recipient: AccountInfo<'info>,
+    pub arbiter: Signer<'info>,
+}
+
+#[error_code]
+pub enum CustomError {
+    #[msg("escrow has already been released")]
+    AlreadyReleased,
+    #[msg("only the designated arbiter may release this escrow")]
+    NotTheArbiter,
+    #[msg("lamport arithmetic overflow")]
+    Overflow,
+}
+
+/**
+ * WHY THIS WORKS:
+ * 1. has_one reads the arbiter key out of the escrow's own data and
+ *    requires the passed account to match; Signer requires it to have
+ *    signed. Together: "the recorded arbiter authorized this".
+ * 2. The custom error (`@ CustomError::NotTheArbiter`) makes rejected
+ *    releases diagnosable instead of a generic constraint failure.
+ * 3. Every stored authority field should have a constraint that reads
+ *    it — an unenforced field is the bug in the vulnerable version.
+ */
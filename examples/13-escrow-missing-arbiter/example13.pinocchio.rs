@@ -0,0 +1,97 @@
+// Models the escrow release as pure functions over the escrow state and the
+// key that signed the release. The vulnerable check only cares THAT someone
+// signed; the fixed check cares WHO.
+
+type Pubkey = [u8; 32];
+
+#[derive(Clone)]
+struct Escrow {
+    recipient: Pubkey,
+    arbiter: Pubkey,
+    amount: u64,
+    released: bool,
+}
+
+// Mirrors the vulnerable release: any signer may trigger it.
+fn vuln_release(
+    escrow: &mut Escrow,
+    _releaser: &Pubkey,
+    recipient_lamports: &mut u64,
+) -> Result<(), &'static str> {
+    if escrow.released {
+        return Err("already released");
+    }
+    escrow.released = true;
+    *recipient_lamports += escrow.amount;
+    Ok(())
+}
+
+// Mirrors the fix: the releaser must be the recorded arbiter (has_one).
+fn safe_release(
+    escrow: &mut Escrow,
+    releaser: &Pubkey,
+    recipient_lamports: &mut u64,
+) -> Result<(), &'static str> {
+    if *releaser != escrow.arbiter {
+        return Err("not the arbiter");
+    }
+    if escrow.released {
+        return Err("already released");
+    }
+    escrow.released = true;
+    *recipient_lamports += escrow.amount;
+    Ok(())
+}
+
+#[cfg(test)]
+mod pinocchio_tests {
+    use super::*;
+
+    fn key(byte: u8) -> Pubkey {
+        [byte; 32]
+    }
+
+    fn pending_escrow() -> Escrow {
+        Escrow {
+            recipient: key(2),
+            arbiter: key(3),
+            amount: 1_000,
+            released: false,
+        }
+    }
+
+    #[test]
+    fn recipient_can_release_to_themselves_against_the_vuln() {
+        let mut escrow = pending_escrow();
+        let mut recipient_lamports = 0u64;
+
+        // The recipient signs with their OWN key — no arbiter involved —
+        // and walks away with the funds before any ruling.
+        let recipient = key(2);
+        assert_eq!(escrow.recipient, recipient); // a counterparty, not the arbiter
+        vuln_release(&mut escrow, &recipient, &mut recipient_lamports).unwrap();
+        assert!(escrow.released);
+        assert_eq!(recipient_lamports, 1_000);
+    }
+
+    #[test]
+    fn fix_rejects_everyone_but_the_arbiter() {
+        let mut escrow = pending_escrow();
+        let mut recipient_lamports = 0u64;
+
+        // Recipient and depositor are both turned away...
+        for intruder in [key(1), key(2)] {
+            let err = safe_release(&mut escrow, &intruder, &mut recipient_lamports).unwrap_err();
+            assert_eq!(err, "not the arbiter");
+        }
+        assert!(!escrow.released);
+        assert_eq!(recipient_lamports, 0);
+
+        // ...and the arbiter's release goes through exactly once.
+        let arbiter = key(3);
+        safe_release(&mut escrow, &arbiter, &mut recipient_lamports).unwrap();
+        assert_eq!(recipient_lamports, 1_000);
+        let err = safe_release(&mut escrow, &arbiter, &mut recipient_lamports).unwrap_err();
+        assert_eq!(err, "already released");
+    }
+}
@@ -0,0 +1,74 @@
+#![allow(unexpected_cfgs)]
+use anchor_lang::prelude::*;
+
+#[account]
+pub struct Escrow {
+    pub depositor: Pubkey, // who funded the escrow
+    pub recipient: Pubkey, // who the funds are destined for
+    pub arbiter: Pubkey,   // the ONLY party allowed to release
+    pub amount: u64,
+    pub released: bool,
+}
+
+declare_id!("5M49ak7KjoNGaKz637fjfLz7uMStDvMTXJFgPnBcz7sp");
+
+#[program]
+pub mod escrow_vuln {
+    use super::*;
+
+    /// Releases the escrowed funds to the recipient.
+    pub fn release(ctx: Context<ReleaseVuln>) -> Result<()> {
+        let escrow = &mut ctx.accounts.escrow;
+        require!(!escrow.released, CustomError::AlreadyReleased);
+
+        // --- THE VULNERABILITY ---
+        // `releaser` is a Signer, so SOMEBODY signed — but nothing compares
+        // that somebody against `escrow.arbiter`. The whole point of an
+        // escrow is that a specific neutral party decides when the funds
+        // move; here ANY keypair on the network can trigger the release.
+        //
+        // The recipient simply signs with their own key and releases the
+        // funds to themselves before the arbiter has ruled — or the
+        // depositor releases back before delivering. The arbiter field is
+        // decorative.
+        escrow.released = true;
+
+        **ctx.accounts.recipient.lamports.borrow_mut() = ctx
+            .accounts
+            .recipient
+            .lamports()
+            .checked_add(escrow.amount)
+            .ok_or(CustomError::Overflow)?;
+
+        msg!("escrow released by {}", ctx.accounts.releaser.key());
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct ReleaseVuln<'info> {
+    #[account(mut, has_one = recipient)]
+    pub escrow: Account<'info, Escrow>,
+    /// CHECK: lamport destination recorded in the escrow
+    #[account(mut)]
+    pub recipient: AccountInfo<'info>,
+    /// Signs the release — but is never matched against escrow.arbiter!
+    pub releaser: Signer<'info>,
+}
+
+#[error_code]
+pub enum CustomError {
+    #[msg("escrow has already been released")]
+    AlreadyReleased,
+    #[msg("lamport arithmetic overflow")]
+    Overflow,
+}
+
+/**
+ * SUMMARY OF THE BUG:
+ * 1. "Is a signer" is not "is THE signer". The Signer type proves a key
+ *    signed; only a comparison proves it is the right key.
+ * 2. The escrow stores an arbiter field that no constraint ever reads —
+ *    stored authority that isn't enforced is just documentation.
+ * 3. Either counterparty can release prematurely, defeating the escrow.
+ */
@@ -0,0 +1,125 @@
+#![allow(unexpected_cfgs)]
+use anchor_lang::prelude::*;
+
+#[account]
+pub struct Vault {
+    pub owner: Pubkey,
+    pub mint: Pubkey, // which token this vault is scoped to
+    pub amount: u64,
+    pub bump: u8, // recorded once at init, trusted ever after
+}
+
+declare_id!("69eVSGizWwAGrpehqs1P8KuZ5AU1JwctLZzDbLpDmwBg");
+
+pub const VAULT_SEED: &[u8] = b"vault";
+
+/// THE canonical seed list, defined exactly once. Every instruction that
+/// names the vault goes through this function, so the seeds cannot drift
+/// between init and withdraw the way they did in the vulnerable version.
+pub fn vault_seeds<'a>(owner: &'a Pubkey, mint: &'a Pubkey) -> [&'a [u8]; 3] {
+    [VAULT_SEED, owner.as_ref(), mint.as_ref()]
+}
+
+#[program]
+pub mod pda_mismatch_fix {
+    use super::*;
+
+    pub fn initialize_vault(ctx: Context<InitVaultSafe>, amount: u64) -> Result<()> {
+        let vault = &mut ctx.accounts.vault;
+        vault.owner = ctx.accounts.owner.key();
+        vault.mint = ctx.accounts.mint.key();
+        vault.amount = amount;
+        // --- THE FIX, part one ---
+        // Store the canonical bump Anchor found during init. Later
+        // instructions validate with create_program_address against this
+        // stored bump — cheaper than a fresh find_program_address, and it
+        // pins the account to the exact address init created.
+        vault.bump = ctx.bumps.vault;
+        Ok(())
+    }
+
+    pub fn withdraw(ctx: Context<WithdrawSafe>, amount: u64) -> Result<()> {
+        let vault = &mut ctx.accounts.vault;
+        vault.amount = vault
+            .amount
+            .checked_sub(amount)
+            .ok_or(CustomError::InsufficientFunds)?;
+        msg!("withdrew {} from vault {}", amount, vault.key());
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct InitVaultSafe<'info> {
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + 32 + 32 + 8 + 1,
+        seeds = [VAULT_SEED, owner.key().as_ref(), mint.key().as_ref()],
+        bump,
+    )]
+    pub vault: Account<'info, Vault>,
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    /// CHECK: only its key is used as a seed component
+    pub mint: AccountInfo<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawSafe<'info> {
+    // --- THE FIX, part two ---
+    // The full seed list — mint included — matching init exactly, and
+    // `bump = vault.bump` so validation replays the address init actually
+    // created instead of re-deriving and hoping.
+    #[account(
+        mut,
+        has_one = owner,
+        seeds = [VAULT_SEED, owner.key().as_ref(), vault.mint.as_ref()],
+        bump = vault.bump,
+    )]
+    pub vault: Account<'info, Vault>,
+    pub owner: Signer<'info>,
+}
+
+#[error_code]
+pub enum CustomError {
+    #[msg("insufficient funds in vault")]
+    InsufficientFunds,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Both instructions name the vault through the one shared seed list,
+    /// and the stored bump reproduces the init address exactly.
+    #[test]
+    fn stored_bump_replays_the_init_address() {
+        let owner = Pubkey::new_unique();
+        let mint = Pubkey::new_unique();
+
+        // What init derives (and stores the bump of)...
+        let (init_address, stored_bump) =
+            Pubkey::find_program_address(&vault_seeds(&owner, &mint), &ID);
+
+        // ...is exactly what withdraw's `bump = vault.bump` validates.
+        let replayed = Pubkey::create_program_address(
+            &[VAULT_SEED, owner.as_ref(), mint.as_ref(), &[stored_bump]],
+            &ID,
+        )
+        .unwrap();
+        assert_eq!(init_address, replayed);
+    }
+}
+
+/**
+ * WHY THIS WORKS:
+ * 1. One function owns the seed list; init and withdraw both call it, so
+ *    the derivation cannot silently diverge between instructions.
+ * 2. The stored bump turns later validations into a replay of the exact
+ *    address init created — no re-derivation to get wrong, and no
+ *    find_program_address loop burning compute.
+ * 3. If the seed list ever changes, it changes in one place and every
+ *    instruction moves together.
+ */
@@ -0,0 +1,116 @@
+// Models PDA derivation as a pure function over a seed list, and the two
+// programs as lookups into the map of accounts that actually exist. The
+// vulnerable withdraw re-derives with a shorter seed list and finds
+// nothing; the fix derives through one shared function and stores the
+// bump, so init and withdraw always name the same account.
+
+use std::collections::HashMap;
+
+type Address = u64;
+
+// Stand-in for find_program_address: deterministic, and any change to the
+// seed list changes the result — which is the only property the bug needs.
+fn derive(seeds: &[&[u8]]) -> (Address, u8) {
+    let mut address: Address = 0xcbf2_9ce4_8422_2325;
+    for seed in seeds {
+        for byte in *seed {
+            address = (address ^ *byte as Address).wrapping_mul(0x100_0000_01b3);
+        }
+        address = address.wrapping_mul(0x100_0000_01b3); // seed boundary
+    }
+    (address, (address % 256) as u8)
+}
+
+#[derive(Clone)]
+struct Vault {
+    amount: u64,
+    bump: u8,
+}
+
+const VAULT_SEED: &[u8] = b"vault";
+
+// The vulnerable program: init and withdraw each spell the seeds out
+// locally, and withdraw's copy lost the mint component.
+fn vuln_init(accounts: &mut HashMap<Address, Vault>, owner: &[u8; 32], mint: &[u8; 32], amount: u64) {
+    let (address, bump) = derive(&[VAULT_SEED, owner, mint]);
+    accounts.insert(address, Vault { amount, bump });
+}
+
+fn vuln_withdraw(
+    accounts: &mut HashMap<Address, Vault>,
+    owner: &[u8; 32],
+    amount: u64,
+) -> Result<(), &'static str> {
+    // Re-derived from memory — mint forgotten.
+    let (address, _bump) = derive(&[VAULT_SEED, owner]);
+    let vault = accounts.get_mut(&address).ok_or("seeds constraint violated")?;
+    vault.amount = vault.amount.checked_sub(amount).ok_or("insufficient funds")?;
+    Ok(())
+}
+
+// The fix: one function owns the seed list, and the bump stored at init
+// lets withdraw replay the exact init address.
+fn vault_seeds<'a>(owner: &'a [u8; 32], mint: &'a [u8; 32]) -> [&'a [u8]; 3] {
+    [VAULT_SEED, owner, mint]
+}
+
+fn safe_init(accounts: &mut HashMap<Address, Vault>, owner: &[u8; 32], mint: &[u8; 32], amount: u64) {
+    let (address, bump) = derive(&vault_seeds(owner, mint));
+    accounts.insert(address, Vault { amount, bump });
+}
+
+fn safe_withdraw(
+    accounts: &mut HashMap<Address, Vault>,
+    owner: &[u8; 32],
+    mint: &[u8; 32],
+    amount: u64,
+) -> Result<u8, &'static str> {
+    let (address, derived_bump) = derive(&vault_seeds(owner, mint));
+    let vault = accounts.get_mut(&address).ok_or("seeds constraint violated")?;
+    if vault.bump != derived_bump {
+        return Err("bump mismatch");
+    }
+    vault.amount = vault.amount.checked_sub(amount).ok_or("insufficient funds")?;
+    Ok(vault.bump)
+}
+
+#[cfg(test)]
+mod pinocchio_tests {
+    use super::*;
+
+    fn key(byte: u8) -> [u8; 32] {
+        [byte; 32]
+    }
+
+    #[test]
+    fn vuln_withdraw_can_never_find_the_vault_it_funded() {
+        let mut accounts = HashMap::new();
+        let (owner, mint) = (key(1), key(2));
+
+        vuln_init(&mut accounts, &owner, &mint, 1_000);
+        assert_eq!(accounts.len(), 1, "the deposit landed");
+
+        // The withdraw derivation points at an address where nothing
+        // lives — and no input the owner can supply will change that.
+        let err = vuln_withdraw(&mut accounts, &owner, 500).unwrap_err();
+        assert_eq!(err, "seeds constraint violated");
+
+        // The funds are still there. They always will be.
+        assert_eq!(accounts.values().next().unwrap().amount, 1_000);
+    }
+
+    #[test]
+    fn fix_derives_consistently_and_replays_the_stored_bump() {
+        let mut accounts = HashMap::new();
+        let (owner, mint) = (key(1), key(2));
+
+        safe_init(&mut accounts, &owner, &mint, 1_000);
+        let stored_bump = safe_withdraw(&mut accounts, &owner, &mint, 400).unwrap();
+
+        // The bump handed back is the one init recorded, and the balance
+        // actually moved.
+        let (_, init_bump) = derive(&vault_seeds(&owner, &mint));
+        assert_eq!(stored_bump, init_bump);
+        assert_eq!(accounts.values().next().unwrap().amount, 600);
+    }
+}
@@ -0,0 +1,132 @@
+#![allow(unexpected_cfgs)]
+use anchor_lang::prelude::*;
+
+#[account]
+pub struct Vault {
+    pub owner: Pubkey,
+    pub mint: Pubkey, // which token this vault is scoped to
+    pub amount: u64,
+    // Note what is MISSING: the bump is never stored. Every instruction
+    // re-derives the address from scratch — and has to get the seeds
+    // right from memory, every time.
+}
+
+declare_id!("7vbovt3N6MzJGDjRz5eTsTMLKivZXSmNyt1ncvfkR56X");
+
+pub const VAULT_SEED: &[u8] = b"vault";
+
+/// The seeds `initialize_vault` derives the PDA with: one vault per
+/// (owner, mint) pair.
+pub fn init_vault_seeds<'a>(owner: &'a Pubkey, mint: &'a Pubkey) -> [&'a [u8]; 3] {
+    [VAULT_SEED, owner.as_ref(), mint.as_ref()]
+}
+
+/// The seeds `withdraw` re-derives with. Spot the difference: the mint
+/// component has been forgotten.
+pub fn withdraw_vault_seeds(owner: &Pubkey) -> [&[u8]; 2] {
+    [VAULT_SEED, owner.as_ref()]
+}
+
+#[program]
+pub mod pda_mismatch_vuln {
+    use super::*;
+
+    /// Creates the vault at the PDA derived from (owner, mint).
+    pub fn initialize_vault(ctx: Context<InitVaultVuln>, amount: u64) -> Result<()> {
+        let vault = &mut ctx.accounts.vault;
+        vault.owner = ctx.accounts.owner.key();
+        vault.mint = ctx.accounts.mint.key();
+        vault.amount = amount;
+        Ok(())
+    }
+
+    /// Withdraws from the vault — or would, if it could ever find it.
+    pub fn withdraw(ctx: Context<WithdrawVuln>, amount: u64) -> Result<()> {
+        let vault = &mut ctx.accounts.vault;
+        vault.amount = vault
+            .amount
+            .checked_sub(amount)
+            .ok_or(CustomError::InsufficientFunds)?;
+        msg!("withdrew {} from vault {}", amount, vault.key());
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct InitVaultVuln<'info> {
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + 32 + 32 + 8,
+        seeds = [VAULT_SEED, owner.key().as_ref(), mint.key().as_ref()],
+        bump,
+    )]
+    pub vault: Account<'info, Vault>,
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    /// CHECK: only its key is used as a seed component
+    pub mint: AccountInfo<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawVuln<'info> {
+    // --- THE VULNERABILITY ---
+    // The seeds here were retyped from memory, and the mint component got
+    // lost. Anchor dutifully derives [b"vault", owner] — a DIFFERENT
+    // address from the one init created at [b"vault", owner, mint] — and
+    // rejects the real vault with ConstraintSeeds. There is no account at
+    // the address this constraint expects, so NO input satisfies it.
+    //
+    // Nothing is stolen. The failure mode is worse in a way: every
+    // deposit the program ever accepted is now unreachable, permanently,
+    // and the bug only surfaces on the first withdrawal attempt.
+    #[account(
+        mut,
+        has_one = owner,
+        seeds = [VAULT_SEED, owner.key().as_ref()],
+        bump,
+    )]
+    pub vault: Account<'info, Vault>,
+    pub owner: Signer<'info>,
+}
+
+#[error_code]
+pub enum CustomError {
+    #[msg("insufficient funds in vault")]
+    InsufficientFunds,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The two instructions derive from different seed lists, so they can
+    /// never agree on an address — the withdraw constraint is asking for
+    /// an account that was never created.
+    #[test]
+    fn init_and_withdraw_derive_different_addresses() {
+        let owner = Pubkey::new_unique();
+        let mint = Pubkey::new_unique();
+
+        let (init_address, _) = Pubkey::find_program_address(&init_vault_seeds(&owner, &mint), &ID);
+        let (withdraw_address, _) =
+            Pubkey::find_program_address(&withdraw_vault_seeds(&owner), &ID);
+
+        assert_ne!(
+            init_address, withdraw_address,
+            "the vault init creates is not the vault withdraw looks for"
+        );
+    }
+}
+
+/**
+ * SUMMARY OF THE BUG:
+ * 1. A PDA is its seeds. Re-deriving in a second instruction with a
+ *    slightly different seed list yields a different address, and the
+ *    seeds constraint rejects the real account every time.
+ * 2. This is a fund-LOCKING bug, not a fund-stealing one: deposits
+ *    succeed, withdrawals are impossible, forever.
+ * 3. Seeds retyped from memory in every instruction are copies waiting
+ *    to drift; nothing forces them to stay in sync.
+ */
@@ -0,0 +1,127 @@
+#![allow(unexpected_cfgs)]
+use anchor_lang::prelude::*;
+
+/// One registry entry: a key plus a little metadata.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct Item {
+    pub key: Pubkey,
+    pub weight: u64,
+}
+
+#[account]
+pub struct Registry {
+    pub owner: Pubkey,
+    pub items: Vec<Item>,
+}
+
+declare_id!("5sZt25nrKRfcTmkrTKTCuy8xGznRxXtib8HoMunyRw9V");
+
+/// discriminator + owner + the Vec's u32 length prefix.
+pub const BASE_SPACE: usize = 8 + 32 + 4;
+/// Serialized size of one `Item`: key (32) + weight (8).
+pub const ITEM_SPACE: usize = 40;
+
+/// --- THE FIX ---
+/// Checked multiplication and addition: any capacity whose space
+/// requirement cannot be represented is refused up front as
+/// `SpaceOverflow`, instead of wrapping into an under-allocation. (The
+/// runtime's own 10 MiB account ceiling would reject these requests too,
+/// but only after the wrap has already produced a bogus small number —
+/// by then the request looks perfectly reasonable.)
+pub fn registry_space(capacity: u64) -> Result<usize> {
+    let items = (capacity as usize)
+        .checked_mul(ITEM_SPACE)
+        .ok_or(CustomError::SpaceOverflow)?;
+    BASE_SPACE
+        .checked_add(items)
+        .ok_or(error!(CustomError::SpaceOverflow))
+}
+
+#[program]
+pub mod registry_fix {
+    use super::*;
+
+    pub fn initialize_registry(ctx: Context<InitRegistrySafe>, _capacity: u64) -> Result<()> {
+        let registry = &mut ctx.accounts.registry;
+        registry.owner = ctx.accounts.owner.key();
+        registry.items = Vec::new();
+        Ok(())
+    }
+
+    pub fn add_item(ctx: Context<MutateRegistrySafe>, item: Item) -> Result<()> {
+        ctx.accounts.registry.items.push(item);
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+#[instruction(capacity: u64)]
+pub struct InitRegistrySafe<'info> {
+    #[account(
+        init,
+        payer = owner,
+        // The ? surfaces SpaceOverflow during account validation, before
+        // anything is allocated.
+        space = registry_space(capacity)?,
+    )]
+    pub registry: Account<'info, Registry>,
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct MutateRegistrySafe<'info> {
+    #[account(mut, has_one = owner)]
+    pub registry: Account<'info, Registry>,
+    pub owner: Signer<'info>,
+}
+
+#[error_code]
+pub enum CustomError {
+    #[msg("The requested capacity overflows the account space calculation.")]
+    SpaceOverflow,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The capacity that wraps the vuln's product to 24 bytes is refused
+    /// here with SpaceOverflow; sane capacities get exact allocations.
+    #[test]
+    fn overflowing_capacity_is_refused() {
+        let capacity: u64 = (u64::MAX / ITEM_SPACE as u64) + 1;
+        let err = registry_space(capacity).unwrap_err();
+        assert!(format!("{}", err).contains("overflows the account space"));
+
+        // Ordinary requests still size exactly: base plus count items.
+        assert_eq!(registry_space(0).unwrap(), BASE_SPACE);
+        assert_eq!(registry_space(100).unwrap(), BASE_SPACE + 100 * ITEM_SPACE);
+
+        // And the allocation really covers what a full registry serializes
+        // to — the invariant the wrapped version breaks.
+        let full = Registry {
+            owner: Pubkey::new_unique(),
+            items: vec![
+                Item {
+                    key: Pubkey::new_unique(),
+                    weight: 7,
+                };
+                100
+            ],
+        };
+        let needed = 8 + full.try_to_vec().unwrap().len();
+        assert_eq!(needed, registry_space(100).unwrap());
+    }
+}
+
+/**
+ * WHY THIS WORKS:
+ * 1. checked_mul/checked_add turn an unrepresentable size into an error
+ *    at validation time, before any lamports move or bytes are allocated.
+ * 2. The space expression in the init constraint propagates the error
+ *    with ?, so the refusal needs no code in the handler body.
+ * 3. The honest-path allocation is byte-exact against the serialized
+ *    form, which is what makes the overflow case detectable at all.
+ */
@@ -0,0 +1,69 @@
+// Models account allocation and serialization as pure functions: the vuln
+// computes the allocation with wrapping arithmetic (what an unchecked `*`
+// does in release builds), the fix with checked arithmetic. The corruption
+// shows up exactly where it would on-chain — not at init, but at the first
+// write that outgrows the wrapped allocation.
+
+const BASE_SPACE: usize = 8 + 32 + 4;
+const ITEM_SPACE: usize = 40;
+
+// Allocation sized by the vulnerable formula.
+fn vuln_allocate(capacity: u64) -> Vec<u8> {
+    let space = BASE_SPACE.wrapping_add((capacity as usize).wrapping_mul(ITEM_SPACE));
+    vec![0u8; space]
+}
+
+// The fixed formula: refuse what cannot be represented.
+fn safe_allocate(capacity: u64) -> Result<Vec<u8>, &'static str> {
+    let items = (capacity as usize)
+        .checked_mul(ITEM_SPACE)
+        .ok_or("space overflow")?;
+    let space = BASE_SPACE.checked_add(items).ok_or("space overflow")?;
+    Ok(vec![0u8; space])
+}
+
+// Serializing `count` items into the account; fails cleanly if the bytes
+// don't fit (Anchor's write-back does the same at instruction exit).
+fn write_items(account: &mut [u8], count: usize) -> Result<(), &'static str> {
+    let needed = BASE_SPACE + count * ITEM_SPACE;
+    if needed > account.len() {
+        return Err("serialization overruns the allocation");
+    }
+    for byte in account[..needed].iter_mut() {
+        *byte = 0xAB; // stand-in for real serialized content
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod pinocchio_tests {
+    use super::*;
+
+    const OVERFLOWING_CAPACITY: u64 = u64::MAX / ITEM_SPACE as u64 + 1;
+
+    #[test]
+    fn vuln_under_allocates_and_the_first_write_overruns() {
+        // Init "succeeds": the wrapped product yields a 68-byte account
+        // for a registry that claims capacity for ~4.6e17 items.
+        let mut account = vuln_allocate(OVERFLOWING_CAPACITY);
+        assert_eq!(account.len(), BASE_SPACE + 24);
+
+        // The deferred failure: writing even one item needs more bytes
+        // than the entire allocation.
+        let err = write_items(&mut account, 1).unwrap_err();
+        assert_eq!(err, "serialization overruns the allocation");
+    }
+
+    #[test]
+    fn fix_refuses_the_overflow_and_sizes_honest_requests_exactly() {
+        assert_eq!(
+            safe_allocate(OVERFLOWING_CAPACITY).unwrap_err(),
+            "space overflow"
+        );
+
+        // A sane capacity allocates exactly enough for a full registry.
+        let mut account = safe_allocate(100).unwrap();
+        assert_eq!(account.len(), BASE_SPACE + 100 * ITEM_SPACE);
+        write_items(&mut account, 100).unwrap();
+    }
+}
@@ -0,0 +1,119 @@
+#![allow(unexpected_cfgs)]
+use anchor_lang::prelude::*;
+
+/// One registry entry: a key plus a little metadata.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct Item {
+    pub key: Pubkey,
+    pub weight: u64,
+}
+
+#[account]
+pub struct Registry {
+    pub owner: Pubkey,
+    pub items: Vec<Item>,
+}
+
+declare_id!("2E68PriNSTLKXV1BkM9oq1pMwLoRjCkXsDNSex3e9gqn");
+
+/// discriminator + owner + the Vec's u32 length prefix.
+pub const BASE_SPACE: usize = 8 + 32 + 4;
+/// Serialized size of one `Item`: key (32) + weight (8).
+pub const ITEM_SPACE: usize = 40;
+
+/// How much space the caller's requested capacity needs.
+///
+/// --- THE VULNERABILITY ---
+/// `capacity * ITEM_SPACE` is unchecked. In a release build the
+/// multiplication silently wraps, so a capacity around 2^64 / 40 produces
+/// a TINY total — the account is allocated a few dozen bytes for a
+/// registry that claims room for quintillions of items. Every later
+/// serialization of a non-trivial item list then runs off the end of the
+/// allocation and fails (or, in hand-rolled serializers, corrupts the
+/// neighbouring bytes).
+pub fn registry_space_vuln(capacity: u64) -> usize {
+    BASE_SPACE + capacity as usize * ITEM_SPACE
+}
+
+#[program]
+pub mod registry_vuln {
+    use super::*;
+
+    /// Creates the registry sized for `capacity` items.
+    pub fn initialize_registry(ctx: Context<InitRegistryVuln>, _capacity: u64) -> Result<()> {
+        let registry = &mut ctx.accounts.registry;
+        registry.owner = ctx.accounts.owner.key();
+        registry.items = Vec::new();
+        Ok(())
+    }
+
+    /// Appends an item; the capacity paid for at init is assumed to exist.
+    pub fn add_item(ctx: Context<MutateRegistryVuln>, item: Item) -> Result<()> {
+        ctx.accounts.registry.items.push(item);
+        // The write-back at instruction exit serializes the whole Vec into
+        // the under-allocated account — and dies there, not here.
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+#[instruction(capacity: u64)]
+pub struct InitRegistryVuln<'info> {
+    #[account(
+        init,
+        payer = owner,
+        // The wrapped product lands here as an absurdly small allocation.
+        space = registry_space_vuln(capacity),
+    )]
+    pub registry: Account<'info, Registry>,
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct MutateRegistryVuln<'info> {
+    #[account(mut, has_one = owner)]
+    pub registry: Account<'info, Registry>,
+    pub owner: Signer<'info>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A capacity just past 2^64 / ITEM_SPACE wraps the product to almost
+    /// nothing. (Mirrored with `wrapping_*` for the same reason the
+    /// arithmetic example's tests do: this workspace builds with overflow
+    /// checks on, while the careless release profile being modelled wraps.)
+    #[test]
+    fn huge_capacity_wraps_to_a_tiny_allocation() {
+        let capacity: u64 = (u64::MAX / ITEM_SPACE as u64) + 1;
+        let space = BASE_SPACE.wrapping_add((capacity as usize).wrapping_mul(ITEM_SPACE));
+
+        // 461 quadrillion items "fit" in 68 bytes.
+        assert_eq!(space, BASE_SPACE + 24);
+
+        // The first real write needs more room than the whole allocation:
+        // one item alone serializes past the wrapped size.
+        let one_item = Registry {
+            owner: Pubkey::new_unique(),
+            items: vec![Item {
+                key: Pubkey::new_unique(),
+                weight: 1,
+            }],
+        };
+        let needed = 8 + one_item.try_to_vec().unwrap().len();
+        assert!(needed > space, "serialization must overrun the allocation");
+    }
+}
+
+/**
+ * SUMMARY OF THE BUG:
+ * 1. `base + count * item_size` is attacker arithmetic: the caller picks
+ *    `count`, and an unchecked product wraps to whatever they like.
+ * 2. The failure is deferred — init succeeds, the corruption surfaces on
+ *    the first serialization that outgrows the wrapped allocation.
+ * 3. Size calculations deserve the same checked treatment as balances;
+ *    both are u64 math driven by untrusted input.
+ */
@@ -0,0 +1,248 @@
+#![allow(unexpected_cfgs)]
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
+
+declare_id!("9sxcCGCqvgHdqR8tqvY9P7wJobgP91vPQc1RFC4scTDj");
+
+pub const DISTRIBUTOR_SEED: &[u8] = b"distributor";
+
+#[program]
+pub mod ata_validation_fix {
+    use super::*;
+
+    /// Pays `amount` reward tokens to the claimant's associated token
+    /// account for the reward mint — and, thanks to the constraints below,
+    /// to nowhere else.
+    pub fn claim_rewards(ctx: Context<ClaimRewards>, amount: u64) -> Result<()> {
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.reward_vault.to_account_info(),
+                    to: ctx.accounts.destination.to_account_info(),
+                    authority: ctx.accounts.distributor.to_account_info(),
+                },
+                &[&[DISTRIBUTOR_SEED, &[ctx.bumps.distributor]]],
+            ),
+            amount,
+        )?;
+
+        msg!(
+            "paid {} reward tokens to {}",
+            amount,
+            ctx.accounts.destination.key()
+        );
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct ClaimRewards<'info> {
+    // Typed as TokenAccount: owner (the spl-token program) and layout are
+    // verified, so a garbage vault can't even enter the handler.
+    #[account(mut)]
+    pub reward_vault: Account<'info, TokenAccount>,
+
+    // --- THE FIX ---
+    // `associated_token::mint` + `associated_token::authority` make Anchor
+    // re-derive the one address the ATA program would create for
+    // (claimant, reward_mint) and compare it against this account, on top
+    // of checking the stored mint matches. An attacker's own token account
+    // sits at a different address and is rejected during validation —
+    // before the handler, and before any tokens move.
+    #[account(
+        mut,
+        associated_token::mint = reward_mint,
+        associated_token::authority = claimant,
+    )]
+    pub destination: Account<'info, TokenAccount>,
+
+    pub claimant: Signer<'info>,
+
+    pub reward_mint: Account<'info, Mint>,
+
+    /// CHECK: PDA with authority over the reward vault; signs the transfer
+    #[account(seeds = [DISTRIBUTOR_SEED], bump)]
+    pub distributor: AccountInfo<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_lang::solana_program::account_info::AccountInfo;
+    use anchor_lang::solana_program::clock::Epoch;
+    use anchor_spl::associated_token::get_associated_token_address;
+    use std::collections::BTreeSet;
+
+    fn make_account(
+        key: Pubkey,
+        owner: Pubkey,
+        is_signer: bool,
+        is_writable: bool,
+        data: Vec<u8>,
+    ) -> AccountInfo<'static> {
+        AccountInfo::new(
+            Box::leak(Box::new(key)),
+            is_signer,
+            is_writable,
+            Box::leak(Box::new(1_000_000_000u64)),
+            Box::leak(data.into_boxed_slice()),
+            Box::leak(Box::new(owner)),
+            false,
+            Epoch::default(),
+        )
+    }
+
+    fn make_program(key: Pubkey) -> AccountInfo<'static> {
+        AccountInfo::new(
+            Box::leak(Box::new(key)),
+            false,
+            false,
+            Box::leak(Box::new(1u64)),
+            Box::leak(Vec::new().into_boxed_slice()),
+            Box::leak(Box::new(Pubkey::new_unique())),
+            true,
+            Epoch::default(),
+        )
+    }
+
+    /// Raw spl-token Mint layout: COption authority, supply, decimals,
+    /// initialized, COption freeze authority = 82 bytes.
+    fn mint_bytes() -> Vec<u8> {
+        let mut data = Vec::with_capacity(82);
+        data.extend_from_slice(&[1, 0, 0, 0]); // mint_authority: Some
+        data.extend_from_slice(Pubkey::new_unique().as_ref());
+        data.extend_from_slice(&1_000_000u64.to_le_bytes()); // supply
+        data.push(6); // decimals
+        data.push(1); // is_initialized
+        data.extend_from_slice(&[0u8; 36]); // freeze_authority: None
+        data
+    }
+
+    /// Raw spl-token Account layout: mint, owner, amount, COption delegate,
+    /// state, COption is_native, delegated_amount, COption close = 165 bytes.
+    fn token_account_bytes(mint: Pubkey, owner: Pubkey, amount: u64) -> Vec<u8> {
+        let mut data = Vec::with_capacity(165);
+        data.extend_from_slice(mint.as_ref());
+        data.extend_from_slice(owner.as_ref());
+        data.extend_from_slice(&amount.to_le_bytes());
+        data.extend_from_slice(&[0u8; 36]); // delegate: None
+        data.push(1); // state: Initialized
+        data.extend_from_slice(&[0u8; 12]); // is_native: None
+        data.extend_from_slice(&0u64.to_le_bytes()); // delegated_amount
+        data.extend_from_slice(&[0u8; 36]); // close_authority: None
+        data
+    }
+
+    /// Runs account validation with the destination at the canonical ATA
+    /// address or an attacker-chosen one, with the token account's stored
+    /// owner field either the claimant or someone else.
+    fn run_validation(canonical_address: bool, owner_is_claimant: bool) -> Result<()> {
+        let program_id = crate::ID;
+        let claimant = Pubkey::new_unique();
+        let reward_mint = Pubkey::new_unique();
+        let (distributor, _bump) = Pubkey::find_program_address(&[DISTRIBUTOR_SEED], &program_id);
+
+        let destination_key = if canonical_address {
+            get_associated_token_address(&claimant, &reward_mint)
+        } else {
+            Pubkey::new_unique()
+        };
+        let destination_owner = if owner_is_claimant {
+            claimant
+        } else {
+            Pubkey::new_unique()
+        };
+
+        let infos: &[AccountInfo] = Box::leak(
+            vec![
+                make_account(
+                    Pubkey::new_unique(),
+                    token::ID,
+                    false,
+                    true,
+                    token_account_bytes(reward_mint, distributor, 1_000_000),
+                ),
+                make_account(
+                    destination_key,
+                    token::ID,
+                    false,
+                    true,
+                    token_account_bytes(reward_mint, destination_owner, 0),
+                ),
+                make_account(claimant, Pubkey::new_unique(), true, false, vec![]),
+                make_account(reward_mint, token::ID, false, false, mint_bytes()),
+                make_account(distributor, program_id, false, false, vec![]),
+                make_program(token::ID),
+                make_program(anchor_spl::associated_token::ID),
+            ]
+            .into_boxed_slice(),
+        );
+        let mut infos_ref = infos;
+        let mut bumps = ClaimRewardsBumps { distributor: 0 };
+        ClaimRewards::try_accounts(
+            &program_id,
+            &mut infos_ref,
+            &[],
+            &mut bumps,
+            &mut BTreeSet::new(),
+        )
+        .map(|_| ())
+    }
+
+    /// The same substitution the vulnerable version waves through: the
+    /// attacker's own token account, right mint, owned by the attacker. The
+    /// constraint's owner comparison stops it during validation.
+    #[test]
+    fn attacker_owned_token_account_is_rejected() {
+        match run_validation(false, false) {
+            Err(err) => assert!(
+                format!("{}", err).contains("token owner constraint"),
+                "expected the token-owner comparison to fire, got: {}",
+                err
+            ),
+            Ok(()) => panic!("an attacker-owned destination must fail validation"),
+        }
+    }
+
+    /// The subtler substitution: right mint AND the claimant in the owner
+    /// field, but parked at an arbitrary (non-ATA) address. Only the
+    /// address re-derivation catches this one — which is exactly what
+    /// `associated_token::*` adds over plain `token::*` constraints.
+    #[test]
+    fn non_ata_address_is_rejected_even_with_matching_fields() {
+        match run_validation(false, true) {
+            Err(err) => assert!(
+                format!("{}", err).contains("associated"),
+                "expected an associated-token constraint error, got: {}",
+                err
+            ),
+            Ok(()) => panic!("a non-ATA address must fail validation"),
+        }
+    }
+
+    /// Sanity check on the honest path: the claimant's real ATA — address
+    /// derived by the very function the constraint uses — passes.
+    #[test]
+    fn canonical_ata_passes_validation() {
+        let result = run_validation(true, true);
+        assert!(result.is_ok(), "{:?}", result.err());
+    }
+}
+
+/**
+ * WHY THIS WORKS:
+ * 1. `associated_token::mint`/`associated_token::authority` turn "where do
+ *    the tokens go" from caller-supplied data into a derived fact: Anchor
+ *    recomputes the one ATA address for (claimant, mint) and refuses
+ *    anything else before the handler runs.
+ * 2. Typed `Account<TokenAccount>`/`Account<Mint>` add the owner and
+ *    layout checks the bare AccountInfos skipped, and `Program<Token>`
+ *    pins the token program id the vulnerable version left open.
+ * 3. The CPI goes through anchor-spl's `token::transfer`, so the
+ *    instruction bytes are built by the library instead of by hand.
+ */
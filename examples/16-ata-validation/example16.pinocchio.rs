@@ -0,0 +1,155 @@
+// Models the reward claim as pure functions over a map of token accounts.
+// The vulnerable claim pays whatever account id the caller hands it; the
+// fix re-derives the claimant's associated token account from (owner, mint)
+// and refuses any destination that isn't it — address, stored owner, and
+// stored mint all have to line up.
+
+use std::collections::HashMap;
+
+type Address = u64;
+
+const TOKEN_PROGRAM: &[u8] = b"token-program";
+
+// Stand-in for the ATA derivation: deterministic over (owner, token
+// program, mint), exactly the seed list the real associated-token program
+// uses. Anyone holding the owner and mint can recompute it.
+fn derive_ata(owner: &[u8; 32], mint: &[u8; 32]) -> Address {
+    let mut address: Address = 0xcbf2_9ce4_8422_2325;
+    for seed in [owner.as_slice(), TOKEN_PROGRAM, mint.as_slice()] {
+        for byte in seed {
+            address = (address ^ *byte as Address).wrapping_mul(0x100_0000_01b3);
+        }
+        address = address.wrapping_mul(0x100_0000_01b3); // seed boundary
+    }
+    address
+}
+
+#[derive(Clone)]
+struct TokenAccount {
+    mint: [u8; 32],
+    owner: [u8; 32],
+    amount: u64,
+}
+
+// The vulnerable program: whoever names a destination gets paid there. The
+// claimant's signature authorizes the claim, but nothing ties the payout
+// account to the claimant.
+fn vuln_claim(
+    accounts: &mut HashMap<Address, TokenAccount>,
+    vault: Address,
+    destination: Address,
+    amount: u64,
+) -> Result<(), &'static str> {
+    let vault_account = accounts.get_mut(&vault).ok_or("vault missing")?;
+    vault_account.amount = vault_account
+        .amount
+        .checked_sub(amount)
+        .ok_or("insufficient rewards")?;
+    let destination_account = accounts.get_mut(&destination).ok_or("destination missing")?;
+    destination_account.amount += amount;
+    Ok(())
+}
+
+// The fix: the destination is not an input, it is a conclusion. The claim
+// derives the one ATA for (claimant, mint), demands the supplied account
+// sit exactly there, and cross-checks the stored owner and mint fields the
+// way the associated_token constraints do.
+fn safe_claim(
+    accounts: &mut HashMap<Address, TokenAccount>,
+    vault: Address,
+    destination: Address,
+    claimant: &[u8; 32],
+    mint: &[u8; 32],
+    amount: u64,
+) -> Result<(), &'static str> {
+    if destination != derive_ata(claimant, mint) {
+        return Err("destination is not the claimant's associated token account");
+    }
+    {
+        let destination_account = accounts.get(&destination).ok_or("destination missing")?;
+        if destination_account.owner != *claimant {
+            return Err("token owner constraint violated");
+        }
+        if destination_account.mint != *mint {
+            return Err("token mint constraint violated");
+        }
+    }
+
+    let vault_account = accounts.get_mut(&vault).ok_or("vault missing")?;
+    vault_account.amount = vault_account
+        .amount
+        .checked_sub(amount)
+        .ok_or("insufficient rewards")?;
+    let destination_account = accounts.get_mut(&destination).ok_or("destination missing")?;
+    destination_account.amount += amount;
+    Ok(())
+}
+
+#[cfg(test)]
+mod pinocchio_tests {
+    use super::*;
+
+    fn key(byte: u8) -> [u8; 32] {
+        [byte; 32]
+    }
+
+    const VAULT: Address = 1;
+    const ATTACKER_ACCOUNT: Address = 2;
+
+    fn setup(claimant: &[u8; 32], attacker: &[u8; 32], mint: &[u8; 32]) -> HashMap<Address, TokenAccount> {
+        let mut accounts = HashMap::new();
+        accounts.insert(
+            VAULT,
+            TokenAccount { mint: *mint, owner: key(99), amount: 1_000 },
+        );
+        accounts.insert(
+            ATTACKER_ACCOUNT,
+            TokenAccount { mint: *mint, owner: *attacker, amount: 0 },
+        );
+        accounts.insert(
+            derive_ata(claimant, mint),
+            TokenAccount { mint: *mint, owner: *claimant, amount: 0 },
+        );
+        accounts
+    }
+
+    #[test]
+    fn vuln_pays_the_attacker_account_the_caller_named() {
+        let (claimant, attacker, mint) = (key(1), key(2), key(3));
+        let mut accounts = setup(&claimant, &attacker, &mint);
+
+        // The claim is "for" the claimant, but the destination is the
+        // attacker's own token account — and the vault pays it.
+        vuln_claim(&mut accounts, VAULT, ATTACKER_ACCOUNT, 1_000).unwrap();
+
+        assert_eq!(accounts[&ATTACKER_ACCOUNT].amount, 1_000);
+        assert_eq!(accounts[&derive_ata(&claimant, &mint)].amount, 0);
+        assert_eq!(accounts[&VAULT].amount, 0);
+    }
+
+    #[test]
+    fn fix_rejects_every_substitution_and_pays_only_the_canonical_ata() {
+        let (claimant, attacker, mint) = (key(1), key(2), key(3));
+        let mut accounts = setup(&claimant, &attacker, &mint);
+
+        // The attacker's account is at the wrong address: refused.
+        let err =
+            safe_claim(&mut accounts, VAULT, ATTACKER_ACCOUNT, &claimant, &mint, 1_000).unwrap_err();
+        assert_eq!(err, "destination is not the claimant's associated token account");
+
+        // Even an account AT the derived address is refused if its stored
+        // owner is not the claimant (a squatter created it differently).
+        let squatted = derive_ata(&claimant, &mint);
+        accounts.get_mut(&squatted).unwrap().owner = attacker;
+        let err =
+            safe_claim(&mut accounts, VAULT, squatted, &claimant, &mint, 1_000).unwrap_err();
+        assert_eq!(err, "token owner constraint violated");
+        accounts.get_mut(&squatted).unwrap().owner = claimant;
+
+        // The honest claim, to the real ATA, goes through.
+        safe_claim(&mut accounts, VAULT, squatted, &claimant, &mint, 1_000).unwrap();
+        assert_eq!(accounts[&squatted].amount, 1_000);
+        assert_eq!(accounts[&ATTACKER_ACCOUNT].amount, 0);
+        assert_eq!(accounts[&VAULT].amount, 0);
+    }
+}
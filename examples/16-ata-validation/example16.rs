@@ -0,0 +1,208 @@
+#![allow(unexpected_cfgs)]
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::solana_program::program::invoke_signed;
+
+declare_id!("7LsEeNmJLRSzW5XB4s59ULbd6UqYNQGrX5CzdQraDvhd");
+
+pub const DISTRIBUTOR_SEED: &[u8] = b"distributor";
+
+/// The spl-token and associated-token program ids, spelled out as constants
+/// because this file deliberately takes no anchor-spl dependency. Note the
+/// irony: the program KNOWS both ids — it just never uses them to check
+/// where the rewards are going.
+pub const TOKEN_PROGRAM_ID: Pubkey =
+    Pubkey::from_str_const("TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA");
+pub const ASSOCIATED_TOKEN_PROGRAM_ID: Pubkey =
+    Pubkey::from_str_const("ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL");
+
+/// The one address rewards for (owner, mint) should ever land on: the
+/// owner's associated token account. The ATA program derives it from
+/// exactly these three seeds, so anyone can recompute it — including this
+/// program, which doesn't.
+pub fn expected_reward_ata(owner: &Pubkey, mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[owner.as_ref(), TOKEN_PROGRAM_ID.as_ref(), mint.as_ref()],
+        &ASSOCIATED_TOKEN_PROGRAM_ID,
+    )
+    .0
+}
+
+#[program]
+pub mod ata_validation_vuln {
+    use super::*;
+
+    /// Pays `amount` reward tokens out of the program vault to... whatever
+    /// writable account the transaction named as `destination`.
+    pub fn claim_rewards(ctx: Context<ClaimRewards>, amount: u64) -> Result<()> {
+        // Hand-built spl-token Transfer: instruction 3, amount LE.
+        let mut data = Vec::with_capacity(9);
+        data.push(3);
+        data.extend_from_slice(&amount.to_le_bytes());
+
+        invoke_signed(
+            &Instruction {
+                program_id: ctx.accounts.token_program.key(),
+                accounts: vec![
+                    AccountMeta::new(ctx.accounts.reward_vault.key(), false),
+                    AccountMeta::new(ctx.accounts.destination.key(), false),
+                    AccountMeta::new_readonly(ctx.accounts.distributor.key(), true),
+                ],
+                data,
+            },
+            &[
+                ctx.accounts.reward_vault.to_account_info(),
+                ctx.accounts.destination.to_account_info(),
+                ctx.accounts.distributor.to_account_info(),
+            ],
+            &[&[DISTRIBUTOR_SEED, &[ctx.bumps.distributor]]],
+        )?;
+
+        msg!(
+            "paid {} reward tokens to {}",
+            amount,
+            ctx.accounts.destination.key()
+        );
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct ClaimRewards<'info> {
+    /// CHECK: the program's reward vault token account, owned by the
+    /// distributor PDA
+    #[account(mut)]
+    pub reward_vault: AccountInfo<'info>,
+
+    // --- THE VULNERABILITY ---
+    // The claimant signs, the mint is named, and the program can derive
+    // the claimant's associated token account from those two keys (see
+    // `expected_reward_ata`). But `destination` is a bare AccountInfo with
+    // no constraint relating it to EITHER of them. An attacker front-runs
+    // or replays someone else's claim with their own token account here,
+    // and the vault pays the attacker — the signature and mint checks all
+    // pass, because they never touch this account.
+    /// CHECK: unvalidated — this is the bug
+    #[account(mut)]
+    pub destination: AccountInfo<'info>,
+
+    pub claimant: Signer<'info>,
+
+    /// CHECK: names which reward mint is being claimed; only its key is read
+    pub reward_mint: AccountInfo<'info>,
+
+    /// CHECK: PDA with authority over the reward vault; signs the transfer
+    #[account(seeds = [DISTRIBUTOR_SEED], bump)]
+    pub distributor: AccountInfo<'info>,
+
+    /// CHECK: not even the token program is pinned to `TOKEN_PROGRAM_ID`
+    pub token_program: AccountInfo<'info>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_lang::solana_program::account_info::AccountInfo;
+    use anchor_lang::solana_program::clock::Epoch;
+    use std::collections::BTreeSet;
+
+    fn make_account(
+        key: Pubkey,
+        owner: Pubkey,
+        is_signer: bool,
+        is_writable: bool,
+        data_len: usize,
+    ) -> AccountInfo<'static> {
+        AccountInfo::new(
+            Box::leak(Box::new(key)),
+            is_signer,
+            is_writable,
+            Box::leak(Box::new(1_000_000_000u64)),
+            Box::leak(vec![0u8; data_len].into_boxed_slice()),
+            Box::leak(Box::new(owner)),
+            false,
+            Epoch::default(),
+        )
+    }
+
+    /// Account validation accepts an arbitrary attacker-owned destination:
+    /// every constraint in the context passes with a token account that has
+    /// nothing to do with the claimant or the reward mint.
+    #[test]
+    fn attacker_substituted_destination_passes_validation() {
+        let program_id = crate::ID;
+        let claimant = Pubkey::new_unique();
+        let reward_mint = Pubkey::new_unique();
+        let (distributor, _bump) = Pubkey::find_program_address(&[DISTRIBUTOR_SEED], &program_id);
+
+        // The attacker's own token account — NOT the claimant's ATA.
+        let attacker_destination = Pubkey::new_unique();
+        assert_ne!(
+            attacker_destination,
+            expected_reward_ata(&claimant, &reward_mint)
+        );
+
+        let infos: &[AccountInfo] = Box::leak(
+            vec![
+                make_account(Pubkey::new_unique(), TOKEN_PROGRAM_ID, false, true, 165),
+                make_account(attacker_destination, TOKEN_PROGRAM_ID, false, true, 165),
+                make_account(claimant, Pubkey::new_unique(), true, false, 0),
+                make_account(reward_mint, TOKEN_PROGRAM_ID, false, false, 82),
+                make_account(distributor, program_id, false, false, 0),
+                make_account(TOKEN_PROGRAM_ID, Pubkey::new_unique(), false, false, 0),
+            ]
+            .into_boxed_slice(),
+        );
+        let mut infos_ref = infos;
+        let mut bumps = ClaimRewardsBumps { distributor: 0 };
+        let result = ClaimRewards::try_accounts(
+            &program_id,
+            &mut infos_ref,
+            &[],
+            &mut bumps,
+            &mut BTreeSet::new(),
+        );
+
+        assert!(
+            result.is_ok(),
+            "nothing relates destination to (claimant, mint): {:?}",
+            result.err()
+        );
+    }
+
+    /// The correct address was derivable the whole time — three seeds the
+    /// context already holds. The fix makes Anchor do this derivation.
+    #[test]
+    fn the_canonical_ata_is_a_pure_function_of_claimant_and_mint() {
+        let claimant = Pubkey::new_unique();
+        let mint = Pubkey::new_unique();
+
+        // Deterministic: everyone who derives it agrees.
+        assert_eq!(
+            expected_reward_ata(&claimant, &mint),
+            expected_reward_ata(&claimant, &mint)
+        );
+        // And scoped: a different owner or mint lands elsewhere.
+        assert_ne!(
+            expected_reward_ata(&claimant, &mint),
+            expected_reward_ata(&Pubkey::new_unique(), &mint)
+        );
+        assert_ne!(
+            expected_reward_ata(&claimant, &mint),
+            expected_reward_ata(&claimant, &Pubkey::new_unique())
+        );
+    }
+}
+
+/**
+ * SUMMARY OF THE BUG:
+ * 1. A signer check proves WHO is claiming; it says nothing about WHERE
+ *    the tokens go. The destination must be tied to the claimant and the
+ *    mint, and here it isn't.
+ * 2. The associated token account is derivable from keys already in the
+ *    context — the program simply never derives or compares it, so any
+ *    writable token account is accepted.
+ * 3. Hand-rolled token CPIs with unchecked AccountInfos silently skip
+ *    every safety net anchor-spl's typed accounts would have provided,
+ *    down to the token program id itself.
+ */
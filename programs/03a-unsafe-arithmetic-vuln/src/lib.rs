@@ -5,6 +5,9 @@ use anchor_lang::prelude::*;
 pub struct Vault {
     pub balance: u64,
     pub owner: Pubkey,
+    // Mirrors the fix's per-vault freeze flag so both programs share one
+    // account layout — but no handler here ever reads it.
+    pub frozen: bool,
 }
 
 declare_id!("Ct3nnwHVXu6b7ZSCyXzqwSc5yZ2VtaREVBPKPMJJYYCy");
@@ -16,30 +19,142 @@ pub mod unsafe_arithmetic_vuln {
     pub fn withdraw(ctx: Context<WithdrawVuln>, amount: u64) -> Result<()> {
         let vault = &mut ctx.accounts.vault;
 
-        // --- THE VULNERABILITY ---
-        // This line uses the standard subtraction operator (-=).
-        //
-        // 1. THE BEHAVIOR: 
-        //    In Rust, if this code is compiled in "Release" mode (which is standard 
-        //    for Solana Mainnet deployments), the compiler does NOT include 
-        //    runtime checks for integer underflow. Instead, it uses "Two's Complement" wrapping.
-        //
-        // 2. THE MATH: 
-        //    If vault.balance is 10 and the user requests an amount of 11:
-        //    10 - 11 = -1
-        //    Since balance is a u64 (unsigned 64-bit integer), it cannot represent -1.
-        //    It "wraps around" to the maximum possible value of a u64:
-        //    18,446,744,073,709,551,615
-        //
-        // 3. THE EXPLOIT:
-        //    An attacker with a balance of 0 can withdraw 1 Lamport. 
-        //    The transaction will succeed, and the attacker's vault balance 
-        //    will suddenly become nearly infinite, allowing them to drain 
-        //    every other user's funds from the program.
-        vault.balance -= amount; 
+        // The whole vulnerability lives in the pure function; see its
+        // comments. The handler only loads and stores the balance.
+        vault.balance = apply_withdraw(vault.balance, amount);
 
         Ok(())
     }
+
+    pub fn withdraw_signed(ctx: Context<WithdrawVuln>, amount: i64) -> Result<()> {
+        let vault = &mut ctx.accounts.vault;
+
+        vault.balance = apply_withdraw_signed(vault.balance, amount);
+
+        Ok(())
+    }
+
+    pub fn deposit(ctx: Context<DepositVuln>, amount: u64) -> Result<()> {
+        let vault = &mut ctx.accounts.vault;
+
+        vault.balance = apply_deposit(vault.balance, amount);
+
+        Ok(())
+    }
+
+    /// Deposits a user-facing whole-token amount, scaling it to base units
+    /// inside the handler. The scaling is where this one goes wrong; see
+    /// [`scale_amount`].
+    pub fn deposit_tokens(
+        ctx: Context<DepositVuln>,
+        whole_tokens: u64,
+        decimals: u8,
+    ) -> Result<()> {
+        let vault = &mut ctx.accounts.vault;
+
+        vault.balance = apply_deposit(vault.balance, scale_amount(whole_tokens, decimals));
+
+        Ok(())
+    }
+
+    pub fn transfer_vault_authority(
+        ctx: Context<TransferAuthorityVuln>,
+        new_owner: Pubkey,
+    ) -> Result<()> {
+        let vault = &mut ctx.accounts.vault;
+
+        // --- THE VULNERABILITY (authority flavor) ---
+        // The context below demands a signature — from ANYONE. The vault's
+        // recorded owner is never consulted, so whoever signs first simply
+        // names themselves (or an accomplice) as the new owner and walks
+        // off with the balance-mutating instructions above.
+        vault.owner = new_owner;
+
+        Ok(())
+    }
+}
+
+// The balance arithmetic is extracted into pure functions so tests exercise
+// the EXACT expressions the handlers run — no AccountInfo scaffolding, and no
+// hand-mirrored copy of the math that could drift from the real thing.
+
+/// The balance math behind `withdraw`.
+///
+/// --- THE VULNERABILITY ---
+/// This uses the standard subtraction operator.
+///
+/// 1. THE BEHAVIOR:
+///    In Rust, if this code is compiled in "Release" mode (which is standard
+///    for Solana Mainnet deployments), the compiler does NOT include
+///    runtime checks for integer underflow. Instead, it uses "Two's Complement" wrapping.
+///
+/// 2. THE MATH:
+///    If balance is 10 and the user requests an amount of 11:
+///    10 - 11 = -1
+///    Since balance is a u64 (unsigned 64-bit integer), it cannot represent -1.
+///    It "wraps around" to the maximum possible value of a u64:
+///    18,446,744,073,709,551,615
+///
+/// 3. THE EXPLOIT:
+///    An attacker with a balance of 0 can withdraw 1 Lamport.
+///    The transaction will succeed, and the attacker's vault balance
+///    will suddenly become nearly infinite, allowing them to drain
+///    every other user's funds from the program.
+pub fn apply_withdraw(balance: u64, amount: u64) -> u64 {
+    balance - amount
+}
+
+/// The balance math behind `withdraw_signed`.
+///
+/// --- SIGN-CONFUSION VARIANT ---
+/// The instruction argument arrives as an i64 (say, from a client SDK
+/// that models deltas), and the program casts it straight to u64:
+///
+///   -1 as u64 == 18_446_744_073_709_551_615 (u64::MAX)
+///
+/// A "negative withdrawal" therefore becomes an astronomically large
+/// amount. Combined with the unchecked subtraction, withdrawing -1 from
+/// any balance wraps the vault to balance + 1 — the attacker DEPOSITS by
+/// withdrawing a negative number.
+pub fn apply_withdraw_signed(balance: u64, amount: i64) -> u64 {
+    balance.wrapping_sub(amount as u64)
+}
+
+/// The balance math behind `deposit`. Same unsafe pattern on the way in:
+/// unchecked addition and no upper bound on the balance. A balance parked
+/// near u64::MAX overflows here, and there is no business-rule cap to stop
+/// a vault from growing into that danger zone in the first place.
+pub fn apply_deposit(balance: u64, amount: u64) -> u64 {
+    balance + amount
+}
+
+/// The unit conversion behind `deposit_tokens`: raw multiplication by the
+/// token's scale factor. The overflow here happens BEFORE any balance math
+/// — a whole-token amount above `u64::MAX / 10^decimals` wraps to some
+/// small residue in release builds, so the user is credited a sliver of
+/// what they sent. The fix converts through `common::parse_amount`, which
+/// refuses the conversion instead.
+pub fn scale_amount(whole_tokens: u64, decimals: u8) -> u64 {
+    whole_tokens.wrapping_mul(10u64.wrapping_pow(u32::from(decimals)))
+}
+
+/// Self-description for the workspace doc generator (rendered by
+/// [`common::VulnInfo::describe`]).
+pub const VULN_INFO: common::VulnInfo = common::VulnInfo {
+    name: "unsafe-arithmetic",
+    severity: "high",
+    summary: "withdraw subtracts with the plain `-` operator and \
+withdraw_signed casts an i64 amount straight to u64, so in an unchecked build \
+a zero-balance vault wraps to u64::MAX on a 1-lamport withdrawal and a \
+negative amount deposits by wrapping upward.",
+    mitigation: "The fix routes every balance change through checked_sub / \
+checked_add with typed errors, rejects negative amounts before converting, \
+and caps balances far below the integer range.",
+};
+
+/// Renders [`VULN_INFO`] as generated documentation.
+pub fn describe() -> String {
+    VULN_INFO.describe()
 }
 
 #[cfg(test)]
@@ -48,7 +163,7 @@ mod tests {
 
     #[test]
     fn vuln_wraps_on_underflow() {
-        let mut vault = Vault { balance: 10, owner: Pubkey::new_unique() };
+        let mut vault = Vault { balance: 10, owner: Pubkey::new_unique(), frozen: false };
         let amount = 11u64;
 
         // Mirror vulnerable logic: wrapping subtraction in release mode.
@@ -56,13 +171,184 @@ mod tests {
 
         assert_eq!(vault.balance, u64::MAX);
     }
+
+    /// Counterpart to the fix's `paused_protocol_blocks_withdraw`: there is
+    /// no Settings account anywhere in this program's contexts, so the
+    /// admin's global pause simply does not apply here.
+    #[test]
+    fn vuln_keeps_withdrawing_while_the_protocol_is_paused() {
+        let mut vault = Vault { balance: 1_000, owner: Pubkey::new_unique(), frozen: false };
+
+        let protocol_paused = true;
+        vault.balance = vault.balance.wrapping_sub(100);
+
+        assert!(protocol_paused);
+        assert_eq!(vault.balance, 900);
+    }
+
+    /// Same story for the per-vault freeze: the field is in the layout, the
+    /// owner can set it — and neither withdraw path ever consults it. The
+    /// fix's `frozen_vault_blocks_withdrawals_until_thawed` is the contrast.
+    #[test]
+    fn vuln_keeps_withdrawing_from_a_frozen_vault() {
+        let mut vault = Vault { balance: 1_000, owner: Pubkey::new_unique(), frozen: true };
+
+        vault.balance = apply_withdraw(vault.balance, 100);
+
+        assert!(vault.frozen);
+        assert_eq!(vault.balance, 900);
+    }
+
+    /// The raw scaling multiply wraps long before the balance math gets a
+    /// say: a whole-token amount past `u64::MAX / 10^9` comes out of the
+    /// conversion as a small residue, so the deposit quietly books a
+    /// fraction of what was sent. The fix's `deposit_tokens` refuses the
+    /// same input through `common::parse_amount`.
+    #[test]
+    fn scaling_overflow_wraps_the_deposited_amount() {
+        let whole = u64::MAX / 1_000_000_000 + 1; // one past the 9-decimal max
+
+        let scaled = scale_amount(whole, 9);
+        // Wrapped: nowhere near whole * 10^9, and vastly SMALLER than even
+        // the unscaled request.
+        assert!(scaled < whole);
+        assert_eq!(scaled, whole.wrapping_mul(1_000_000_000));
+
+        // In-range conversions still scale normally — the wrap only bites
+        // on exactly the inputs nobody tests by hand.
+        assert_eq!(scale_amount(5, 9), 5_000_000_000);
+    }
+
+    /// The `-=` in `withdraw` is profile-dependent: with overflow checks on
+    /// (debug builds, i.e. `cfg!(debug_assertions)`) it panics on underflow,
+    /// without them (a default release build, how careless programs ship to
+    /// mainnet) it silently wraps. This exercises the literal operator under
+    /// whichever behavior the test binary was compiled with, so both sides
+    /// of the matrix stay covered instead of only the wrap being asserted
+    /// through `wrapping_sub`.
+    ///
+    /// Note: this workspace deliberately sets `overflow-checks = true` for
+    /// the release profile too — that is itself the blanket mitigation — so
+    /// the check is probed at runtime rather than inferred from the profile.
+    #[test]
+    fn underflow_behavior_matches_the_build_profile() {
+        let run_withdraw = || {
+            // black_box keeps the compiler from proving the underflow at
+            // compile time (which would be a hard error). This is the very
+            // function the handler delegates to, not a mirror of it.
+            apply_withdraw(std::hint::black_box(10), std::hint::black_box(11))
+        };
+
+        let overflow_checks_enabled =
+            std::panic::catch_unwind(|| std::hint::black_box(10u64) - std::hint::black_box(11))
+                .is_err();
+
+        if overflow_checks_enabled {
+            // Checked build: the runtime overflow check panics, aborting the
+            // transaction — which masks the bug during local (debug) testing.
+            let panicked = std::panic::catch_unwind(run_withdraw).is_err();
+            assert!(panicked, "checked builds must panic on u64 underflow");
+        } else {
+            // Unchecked build: two's-complement wrap, the exploitable path.
+            assert_eq!(run_withdraw(), u64::MAX);
+        }
+    }
+
+    #[test]
+    fn negative_withdrawal_becomes_a_huge_amount() {
+        // The cast at the heart of the sign-confusion bug.
+        let amount: i64 = -1;
+        assert_eq!(amount as u64, u64::MAX);
+
+        // Withdrawing -1 wraps the balance UP by one: a deposit disguised
+        // as a withdrawal. Asserted against the handler's own pure function.
+        assert_eq!(apply_withdraw_signed(10, -1), 11);
+    }
+
+    /// The pure functions on their honest paths: with valid inputs all
+    /// three behave like ordinary bookkeeping, which is exactly why the
+    /// unchecked operators survive review.
+    #[test]
+    fn pure_functions_agree_with_plain_arithmetic_on_valid_input() {
+        assert_eq!(apply_withdraw(1_000, 100), 900);
+        assert_eq!(apply_withdraw_signed(1_000, 100), 900);
+        assert_eq!(apply_deposit(1_000, 100), 1_100);
+    }
+
+    /// And off the honest path: the signed variant wraps deterministically
+    /// (wrapping_sub is explicit in the source, so this holds in every
+    /// build profile, unlike `apply_withdraw`'s profile-dependent `-`).
+    #[test]
+    fn signed_withdrawal_wraps_in_every_build_profile() {
+        assert_eq!(apply_withdraw_signed(0, -1), 1);
+        assert_eq!(apply_withdraw_signed(10, i64::MIN), 10u64.wrapping_sub(i64::MIN as u64));
+    }
+
+    /// `TransferAuthorityVuln` accepts a signature from anybody, so the
+    /// seizure is just an unconditional overwrite of `vault.owner` — the
+    /// fix's counterpart test shows `has_one = owner` refusing the same
+    /// move from a non-owner.
+    #[test]
+    fn vuln_hands_the_vault_to_any_signer() {
+        let owner = Pubkey::new_unique();
+        let attacker = Pubkey::new_unique();
+        let mut vault = Vault { balance: 1_000, owner, frozen: false };
+
+        // The handler never compares the signer against vault.owner; the
+        // attacker signs as themselves and names themselves the new owner.
+        vault.owner = attacker;
+
+        assert_eq!(vault.owner, attacker);
+        // From here every has_one-gated instruction answers to the attacker.
+        assert_ne!(vault.owner, owner);
+    }
+
+    /// The three subtraction flavors behave very differently on underflow:
+    ///
+    /// - `wrapping_sub`: what this vuln effectively does in release mode
+    ///   (wraps around to u64::MAX — the exploitable behavior).
+    /// - `saturating_sub`: what the CPI-reentrancy vuln uses (silently clamps
+    ///   to 0 — hides the error but is NOT the same protection as checked).
+    /// - `checked_sub`: what the arithmetic fix uses (returns None, letting
+    ///   the handler abort the transaction).
+    #[test]
+    fn saturating_wrapping_and_checked_differ_on_underflow() {
+        let balance = 10u64;
+        let amount = 11u64;
+
+        assert_eq!(balance.saturating_sub(amount), 0);
+        assert_eq!(balance.wrapping_sub(amount), u64::MAX);
+        assert_eq!(balance.checked_sub(amount), None);
+
+        // On a valid withdrawal all three agree, which is why the clamping
+        // variants are easy to mistake for real protection.
+        assert_eq!(balance.saturating_sub(4), 6);
+        assert_eq!(balance.wrapping_sub(4), 6);
+        assert_eq!(balance.checked_sub(4), Some(6));
+    }
+}
+
+#[derive(Accounts)]
+pub struct DepositVuln<'info> {
+    #[account(mut, has_one = owner)]
+    pub vault: Account<'info, Vault>,
+    pub owner: Signer<'info>,
 }
 
 #[derive(Accounts)]
 pub struct WithdrawVuln<'info> {
-    // We check that the signer is the owner, but we fail to check 
+    // We check that the signer is the owner, but we fail to check
     // if the owner actually has enough funds for the withdrawal.
     #[account(mut, has_one = owner)]
     pub vault: Account<'info, Vault>,
     pub owner: Signer<'info>,
 }
+
+#[derive(Accounts)]
+pub struct TransferAuthorityVuln<'info> {
+    #[account(mut)]
+    pub vault: Account<'info, Vault>,
+    // A signature, but from nobody in particular — there is no has_one
+    // tying this signer to vault.owner.
+    pub anyone: Signer<'info>,
+}
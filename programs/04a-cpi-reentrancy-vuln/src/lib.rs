@@ -9,6 +9,14 @@ pub struct Vault {
     pub is_locked: bool,
     pub authority: Pubkey,
     pub balance: u64,
+    // Same reserve-floor field the fix enforces — but no path in this
+    // program ever reads it. A stored limit nobody checks limits nothing.
+    pub min_balance: u64,
+    // Whether the pre-transfer hook CPI actually ran on the last withdraw.
+    // The original code discarded the hook result with `.ok()`, so "the
+    // attacker never fired" and "the attacker fired and failed" were
+    // indistinguishable; recording the outcome keeps the demo honest.
+    pub hook_executed: bool,
 }
 
 // VULNERABLE: makes an external CPI before updating state and has no
@@ -29,12 +37,14 @@ pub mod cpi_reentrancy_vuln {
         let vault_info = ctx.accounts.vault.to_account_info();
         let recipient_info = ctx.accounts.recipient.to_account_info();
         let attacker_info = ctx.accounts.attacker_program.to_account_info();
+        let witness_key = ctx.accounts.hook_witness.key();
+        let witness_info = ctx.accounts.hook_witness.to_account_info();
 
         // Now take the mutable borrow for state mutation.
         let vault = &mut ctx.accounts.vault;
 
         // Call attacker hook before state update (vulnerability enabled).
-        invoke(
+        let hook_result = invoke(
             &anchor_lang::solana_program::instruction::Instruction {
                 program_id: ctx.accounts.attacker_program.key(),
                 accounts: vec![
@@ -46,12 +56,17 @@ pub mod cpi_reentrancy_vuln {
                         victim_program,
                         false,
                     ),
+                    // Writable witness the hook stamps on entry — the
+                    // durable proof that attacker code ran mid-withdrawal.
+                    anchor_lang::solana_program::instruction::AccountMeta::new(witness_key, false),
                 ],
-                data: [0].to_vec(), // discriminator for reentrancy_hook
+                data: cpi_reentrancy_attacker::reentrancy_hook_discriminator().to_vec(),
             },
-            &[vault_info.clone(), attacker_info],
-        )
-        .ok(); // Continue even if attacker fails (for demo purposes)
+            &[vault_info.clone(), attacker_info, witness_info],
+        );
+        // Still continue even if the attacker fails (for demo purposes),
+        // but record whether the hook ran instead of discarding the result.
+        record_hook_outcome(vault, hook_result);
 
         // Sends lamports out before updating state (still vulnerable).
         invoke(
@@ -62,6 +77,36 @@ pub mod cpi_reentrancy_vuln {
         vault.balance = vault.balance.saturating_sub(amount);
         Ok(())
     }
+
+    pub fn credit(ctx: Context<CreditVuln>, amount: u64) -> Result<()> {
+        let vault = &mut ctx.accounts.vault;
+
+        // A second balance-mutating path that never looks at `is_locked`.
+        // Even if `withdraw` grew a lock, a hook re-entering through here
+        // walks straight past it — a guard that is set but not checked on
+        // EVERY mutating path is no guard at all.
+        vault.balance = vault.balance.saturating_add(amount);
+
+        Ok(())
+    }
+}
+
+/// Stores the hook CPI's outcome on the vault, replacing the old `.ok()`
+/// that swallowed it. A garbage attacker program id makes the invoke fail
+/// (e.g. `IncorrectProgramId`), which now leaves a visible `false` behind
+/// so tests and observers can tell a silent no-op from an executed hook.
+pub fn record_hook_outcome(
+    vault: &mut Vault,
+    hook_result: std::result::Result<(), anchor_lang::solana_program::program_error::ProgramError>,
+) {
+    vault.hook_executed = hook_result.is_ok();
+}
+
+#[derive(Accounts)]
+pub struct CreditVuln<'info> {
+    #[account(mut, has_one = authority)]
+    pub vault: Account<'info, Vault>,
+    pub authority: Signer<'info>,
 }
 
 #[derive(Accounts)]
@@ -72,14 +117,140 @@ pub struct WithdrawVuln<'info> {
     /// CHECK: simplified recipient for illustration
     #[account(mut)]
     pub recipient: AccountInfo<'info>,
+    /// CHECK: attacker-owned `HookWitness` forwarded to the hook CPI so the
+    /// attacker can stamp proof that it gained control during the withdraw
+    #[account(mut)]
+    pub hook_witness: AccountInfo<'info>,
     /// CHECK: the attacker program that will be called
     pub attacker_program: AccountInfo<'info>,
     pub system_program: Program<'info, System>,
 }
 
+/// Self-description consumed by the doc generator; the markdown shape is
+/// defined once on [`common::VulnInfo`].
+pub const VULN_INFO: common::VulnInfo = common::VulnInfo {
+    name: "cpi-reentrancy",
+    severity: "high",
+    summary: "withdraw invokes a caller-chosen hook program and transfers \
+lamports BEFORE debiting the balance, so a hook that re-enters withdraws \
+against the stale balance and the outer write erases the nested debit -- \
+lamports leave the vault that the books never record.",
+    mitigation: "The fix updates state before any CPI, takes a uniform \
+re-entrancy lock on every mutating path, and only invokes the one notifier \
+program the authority registered.",
+};
+
+/// Renders [`VULN_INFO`] as generated documentation.
+pub fn describe() -> String {
+    VULN_INFO.describe()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use anchor_lang::solana_program::account_info::AccountInfo;
+    use anchor_lang::solana_program::clock::Epoch;
+    use anchor_lang::{AnchorSerialize, Discriminator};
+    use std::collections::BTreeSet;
+
+    fn make_account(
+        key: Pubkey,
+        owner: Pubkey,
+        is_signer: bool,
+        is_writable: bool,
+        data: Vec<u8>,
+    ) -> AccountInfo<'static> {
+        let leaked_key = Box::leak(Box::new(key));
+        let leaked_owner = Box::leak(Box::new(owner));
+        let lamports = Box::leak(Box::new(1_000_000_000u64));
+        let data: &'static mut [u8] = Box::leak(data.into_boxed_slice());
+
+        AccountInfo::new(
+            leaked_key,
+            is_signer,
+            is_writable,
+            lamports,
+            data,
+            leaked_owner,
+            false,
+            Epoch::default(),
+        )
+    }
+
+    fn serialize_vault(vault: &Vault) -> Vec<u8> {
+        let mut data = Vault::DISCRIMINATOR.to_vec();
+        data.extend_from_slice(&vault.try_to_vec().unwrap());
+        data
+    }
+
+    /// The vuln is PURELY the CPI ordering and the missing guard — the
+    /// `has_one = authority` constraint is present and correct. Pinning
+    /// that down keeps the lessons separate: a non-authority signer is
+    /// rejected here exactly as in the fix, so exploiting this program
+    /// requires the authority's own withdrawal, not a stolen one.
+    #[test]
+    fn vuln_still_rejects_a_non_authority_signer() {
+        let program_id = crate::id();
+        let authority = Pubkey::new_unique();
+
+        let vault_state = Vault {
+            is_locked: false,
+            authority,
+            balance: 1_000,
+            min_balance: 0,
+            hook_executed: false,
+        };
+        let vault_ai = make_account(
+            Pubkey::new_unique(),
+            program_id,
+            false,
+            true,
+            serialize_vault(&vault_state),
+        );
+        // Someone other than the stored authority signs the withdrawal.
+        let intruder_ai = make_account(Pubkey::new_unique(), Pubkey::new_unique(), true, false, vec![]);
+        let recipient_ai = make_account(Pubkey::new_unique(), Pubkey::new_unique(), false, true, vec![]);
+        let witness_ai = make_account(
+            Pubkey::new_unique(),
+            Pubkey::new_from_array(cpi_reentrancy_attacker::id().to_bytes()),
+            false,
+            true,
+            vec![],
+        );
+        let attacker_ai = make_account(Pubkey::new_unique(), Pubkey::new_unique(), false, false, vec![]);
+        let system_ai = AccountInfo::new(
+            Box::leak(Box::new(anchor_lang::solana_program::system_program::ID)),
+            false,
+            false,
+            Box::leak(Box::new(1u64)),
+            Box::leak(Vec::new().into_boxed_slice()),
+            Box::leak(Box::new(Pubkey::new_unique())),
+            true,
+            Epoch::default(),
+        );
+
+        let infos: &[AccountInfo] = Box::leak(
+            vec![vault_ai, intruder_ai, recipient_ai, witness_ai, attacker_ai, system_ai]
+                .into_boxed_slice(),
+        );
+        let mut infos_ref = infos;
+        let mut bumps = WithdrawVulnBumps {};
+        let result = WithdrawVuln::try_accounts(
+            &program_id,
+            &mut infos_ref,
+            &[],
+            &mut bumps,
+            &mut BTreeSet::new(),
+        );
+        match result {
+            Err(err) => assert!(
+                format!("{}", err).contains("has one"),
+                "expected ConstraintHasOne, got: {}",
+                err
+            ),
+            Ok(_) => panic!("a non-authority signer must fail has_one even in the vuln"),
+        }
+    }
 
     #[test]
     fn vuln_allows_stale_write_after_external_call() {
@@ -88,6 +259,8 @@ mod tests {
             is_locked: false,
             authority,
             balance: 1_000,
+            min_balance: 0,
+            hook_executed: false,
         };
 
         // Simulate attacker reducing balance during CPI before state update.
@@ -97,5 +270,408 @@ mod tests {
 
         assert_eq!(final_balance, 900);
         assert_eq!(vault.balance, 500); // stale overwrite risk highlighted
+
+        // The stale outer write is what actually persists.
+        vault.balance = final_balance;
+
+        // Had both withdrawals been booked, the vault would read
+        // 1_000 - 100 - 500 = 400. It reads 900: the nested withdrawal's
+        // 500 lamports left the vault but were never debited — the exact
+        // over-withdrawal magnitude the Pinocchio sim flags with its
+        // assert_ne!(state.balance, 400).
+        let correct_balance = 1_000u64 - 100 - 500;
+        assert_eq!(correct_balance, 400);
+        assert_ne!(vault.balance, correct_balance);
+        assert_eq!(vault.balance - correct_balance, 500); // unbooked drain
+    }
+
+    #[test]
+    fn partial_lock_is_bypassed_through_the_unchecked_path() {
+        let authority = Pubkey::new_unique();
+        let mut vault = Vault {
+            is_locked: true, // an in-flight withdraw holds the lock
+            authority,
+            balance: 1_000,
+            min_balance: 0,
+            hook_executed: false,
+        };
+
+        // The credit path never consults the lock, so the mutation lands
+        // anyway — exactly what an attacker's hook would do mid-withdraw.
+        vault.balance = vault.balance.saturating_add(500);
+        assert_eq!(vault.balance, 1_500);
+        assert!(vault.is_locked); // the "guard" was up the whole time
+    }
+
+    /// With a garbage attacker-program id the hook CPI fails, and instead
+    /// of the old silent `.ok()` the outcome is recorded on the vault: the
+    /// flag reads false, distinguishing "hook never ran" from "hook ran".
+    #[test]
+    fn garbage_attacker_program_leaves_hook_flag_false() {
+        use anchor_lang::solana_program::program_error::ProgramError;
+
+        let mut vault = Vault {
+            is_locked: false,
+            authority: Pubkey::new_unique(),
+            balance: 1_000,
+            min_balance: 0,
+            hook_executed: true, // stale truth from an earlier withdraw
+        };
+
+        // The runtime refuses to invoke a non-executable garbage key; the
+        // recorded outcome must overwrite the stale flag with false.
+        record_hook_outcome(&mut vault, Err(ProgramError::IncorrectProgramId));
+        assert!(!vault.hook_executed);
+
+        // And a hook that genuinely ran reads back as true.
+        record_hook_outcome(&mut vault, Ok(()));
+        assert!(vault.hook_executed);
+    }
+
+    /// The fix programs gate on the shared Settings.paused flag through
+    /// `common::ensure_not_paused`; this program's accounts structs have no
+    /// settings field at all, so the global pause cannot reach it. The
+    /// mutation lands no matter what the flag says.
+    #[test]
+    fn vuln_withdraw_has_no_pause_to_consult() {
+        let mut vault = Vault {
+            is_locked: false,
+            authority: Pubkey::new_unique(),
+            balance: 1_000,
+            min_balance: 0,
+            hook_executed: false,
+        };
+
+        // Admin flips the protocol-wide pause... somewhere this program
+        // never looks.
+        let protocol_paused = true;
+        vault.balance = vault.balance.saturating_sub(100);
+
+        assert!(protocol_paused);
+        assert_eq!(vault.balance, 900);
+    }
+
+    #[test]
+    fn stored_minimum_is_sailed_straight_past() {
+        let mut vault = Vault {
+            is_locked: false,
+            authority: Pubkey::new_unique(),
+            balance: 1_000,
+            min_balance: 800, // the owner's intended reserve
+            hook_executed: false,
+        };
+
+        // `withdraw` subtracts with no glance at the floor: the same
+        // 300-lamport withdrawal the fix refuses lands the vault at 700.
+        vault.balance = vault.balance.saturating_sub(300);
+        assert_eq!(vault.balance, 700);
+        assert!(vault.balance < vault.min_balance);
+    }
+
+    #[test]
+    fn over_withdrawal_clamps_to_zero_instead_of_erroring() {
+        let mut vault = Vault {
+            is_locked: false,
+            authority: Pubkey::new_unique(),
+            balance: 1_000,
+            min_balance: 0,
+            hook_executed: false,
+        };
+
+        // Ask for five times the balance. `saturating_sub` swallows the
+        // request whole: the books read zero, no error is raised, and the
+        // 4_000-lamport shortfall between what was asked and what existed
+        // vanishes from the record. The fix's `checked_sub` turns this
+        // exact request into InsufficientFunds — the same migration the
+        // arithmetic example (03) makes.
+        vault.balance = vault.balance.saturating_sub(5_000);
+        assert_eq!(vault.balance, 0);
+    }
+
+    /// `withdraw` must forward the witness into the hook CPI. Off-chain
+    /// `invoke` cannot execute the attacker and panics on contact, so the
+    /// panic is the proof the hook instruction was dispatched with the
+    /// witness aboard; the stamping itself is covered by the attacker
+    /// crate's own tests, which drive `reentrancy_hook` directly.
+    #[test]
+    fn withdraw_dispatches_the_hook_cpi_with_the_witness() {
+        use std::panic::{catch_unwind, AssertUnwindSafe};
+
+        let program_id = crate::id();
+        let authority = Pubkey::new_unique();
+        let vault_state = Vault {
+            is_locked: false,
+            authority,
+            balance: 1_000,
+            min_balance: 0,
+            hook_executed: false,
+        };
+        let vault_ai = Box::leak(Box::new(make_account(
+            Pubkey::new_unique(),
+            program_id,
+            false,
+            true,
+            serialize_vault(&vault_state),
+        )));
+        let authority_ai = Box::leak(Box::new(make_account(
+            authority,
+            Pubkey::new_unique(),
+            true,
+            false,
+            vec![],
+        )));
+        let recipient_ai = Box::leak(Box::new(make_account(
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            false,
+            true,
+            vec![],
+        )));
+        let witness_ai = Box::leak(Box::new(make_account(
+            Pubkey::new_unique(),
+            Pubkey::new_from_array(cpi_reentrancy_attacker::id().to_bytes()),
+            false,
+            true,
+            vec![0u8; 9], // discriminator + hook_ran, all zero
+        )));
+        let attacker_ai = Box::leak(Box::new(make_account(
+            Pubkey::new_from_array(cpi_reentrancy_attacker::id().to_bytes()),
+            Pubkey::new_unique(),
+            false,
+            false,
+            vec![],
+        )));
+        let system_ai = Box::leak(Box::new(AccountInfo::new(
+            Box::leak(Box::new(anchor_lang::solana_program::system_program::ID)),
+            false,
+            false,
+            Box::leak(Box::new(1u64)),
+            Box::leak(Vec::new().into_boxed_slice()),
+            Box::leak(Box::new(Pubkey::new_unique())),
+            true,
+            Epoch::default(),
+        )));
+
+        let mut accounts = WithdrawVuln {
+            vault: Account::try_from(&*vault_ai).unwrap(),
+            authority: Signer::try_from(&*authority_ai).unwrap(),
+            recipient: (*recipient_ai).clone(),
+            hook_witness: (*witness_ai).clone(),
+            attacker_program: (*attacker_ai).clone(),
+            system_program: Program::try_from(&*system_ai).unwrap(),
+        };
+        let dispatched = catch_unwind(AssertUnwindSafe(|| {
+            let ctx = Context::new(&program_id, &mut accounts, &[], WithdrawVulnBumps {});
+            cpi_reentrancy_vuln::withdraw(ctx, 100)
+        }))
+        .is_err();
+        assert!(dispatched, "withdraw must dispatch the hook CPI");
+
+        // This program never writes the witness itself; only the attacker's
+        // hook (running on-chain) stamps it.
+        assert!(witness_ai.try_borrow_data().unwrap().iter().all(|b| *b == 0));
+    }
+
+    /// What the hook sees mid-withdraw HERE, as the counterpart to the fix
+    /// crate's lock-persistence test: the panic at the hook CPI freezes the
+    /// vault account as the attacker would read it, and its persisted
+    /// `is_locked` byte still says false — no lock was ever committed, the
+    /// balance is still the stale pre-debit figure, and the re-entrant
+    /// withdraw walks in unopposed.
+    #[test]
+    fn no_lock_is_persisted_when_the_hook_gains_control() {
+        use std::panic::{catch_unwind, AssertUnwindSafe};
+
+        let program_id = crate::id();
+        let authority = Pubkey::new_unique();
+        let vault_state = Vault {
+            is_locked: false,
+            authority,
+            balance: 1_000,
+            min_balance: 0,
+            hook_executed: false,
+        };
+        let vault_ai = Box::leak(Box::new(make_account(
+            Pubkey::new_unique(),
+            program_id,
+            false,
+            true,
+            serialize_vault(&vault_state),
+        )));
+        let authority_ai = Box::leak(Box::new(make_account(
+            authority,
+            Pubkey::new_unique(),
+            true,
+            false,
+            vec![],
+        )));
+        let recipient_ai = Box::leak(Box::new(make_account(
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            false,
+            true,
+            vec![],
+        )));
+        let witness_ai = Box::leak(Box::new(make_account(
+            Pubkey::new_unique(),
+            Pubkey::new_from_array(cpi_reentrancy_attacker::id().to_bytes()),
+            false,
+            true,
+            vec![0u8; 9],
+        )));
+        let attacker_ai = Box::leak(Box::new(make_account(
+            Pubkey::new_from_array(cpi_reentrancy_attacker::id().to_bytes()),
+            Pubkey::new_unique(),
+            false,
+            false,
+            vec![],
+        )));
+        let system_ai = Box::leak(Box::new(AccountInfo::new(
+            Box::leak(Box::new(anchor_lang::solana_program::system_program::ID)),
+            false,
+            false,
+            Box::leak(Box::new(1u64)),
+            Box::leak(Vec::new().into_boxed_slice()),
+            Box::leak(Box::new(Pubkey::new_unique())),
+            true,
+            Epoch::default(),
+        )));
+
+        let mut accounts = WithdrawVuln {
+            vault: Account::try_from(&*vault_ai).unwrap(),
+            authority: Signer::try_from(&*authority_ai).unwrap(),
+            recipient: (*recipient_ai).clone(),
+            hook_witness: (*witness_ai).clone(),
+            attacker_program: (*attacker_ai).clone(),
+            system_program: Program::try_from(&*system_ai).unwrap(),
+        };
+        let reached_cpi = catch_unwind(AssertUnwindSafe(|| {
+            let ctx = Context::new(&program_id, &mut accounts, &[], WithdrawVulnBumps {});
+            cpi_reentrancy_vuln::withdraw(ctx, 100)
+        }))
+        .is_err();
+        assert!(reached_cpi, "withdraw must reach the hook CPI");
+
+        // The account data as the hook deserializes it: unlocked, undebited.
+        let data = vault_ai.try_borrow_data().unwrap();
+        assert_eq!(data[8], 0, "the vuln never commits a lock before the CPI");
+        let mid_cpi = Vault::try_deserialize(&mut &data[..]).unwrap();
+        assert!(!mid_cpi.is_locked);
+        assert_eq!(mid_cpi.balance, 1_000); // stale: the debit hasn't happened
+    }
+
+    /// An ACCIDENTAL but real protection this vuln goes out of its way to
+    /// forfeit: account data lives in a `RefCell`, so a handler that still
+    /// holds a borrow of the vault when the CPI runs makes every nested
+    /// attempt to borrow the same account fail — the runtime surfaces it as
+    /// `AccountBorrowFailed` instead of letting the re-entrant write land.
+    /// `withdraw` captures keys and drops its borrows BEFORE invoking
+    /// precisely so the CPI can proceed; that "avoid borrow conflicts"
+    /// dance is what hands the attacker a borrowable vault.
+    #[test]
+    fn held_borrow_across_the_cpi_blocks_reentrant_access() {
+        let program_id = crate::id();
+        let vault_state = Vault {
+            is_locked: false,
+            authority: Pubkey::new_unique(),
+            balance: 1_000,
+            min_balance: 0,
+            hook_executed: false,
+        };
+        let vault_ai = Box::leak(Box::new(make_account(
+            Pubkey::new_unique(),
+            program_id,
+            false,
+            true,
+            serialize_vault(&vault_state),
+        )));
+
+        // The outer handler's live borrow, held across the (simulated) CPI.
+        let outer_borrow = vault_ai.try_borrow_mut_data().unwrap();
+
+        // The re-entering attacker cannot touch the data raw...
+        assert!(vault_ai.try_borrow_mut_data().is_err());
+        // ...and cannot even deserialize it into an Account, because
+        // try_from needs a read borrow the outer write borrow excludes.
+        assert!(Account::<Vault>::try_from(&*vault_ai).is_err());
+
+        // Once the outer handler lets go — as this vuln does up front —
+        // the nested access sails through and the drain is back on.
+        drop(outer_borrow);
+        let reentered = Account::<Vault>::try_from(&*vault_ai).unwrap();
+        assert_eq!(reentered.balance, 1_000);
+    }
+
+    /// `saturating_sub` is NOT a re-entrancy defence, and this pins down
+    /// why. The hook re-enters asking for 1_100 against a 1_000 balance:
+    /// the clamp dutifully stops the books at zero — no underflow, no
+    /// error — but the transfer CPI already ran, so the recipient holds
+    /// 1_100 very real lamports. Clamping protects the arithmetic; the
+    /// lamports walk out anyway.
+    #[test]
+    fn clamped_arithmetic_still_loses_funds_to_reentry() {
+        let authority = Pubkey::new_unique();
+        let mut vault = Vault {
+            is_locked: false,
+            authority,
+            balance: 1_000,
+            min_balance: 0,
+            hook_executed: false,
+        };
+        // Real lamports on the accounts (the vault sits well above its
+        // books, as a rent-funded account does).
+        let vault_pre = 10_000u64;
+        let mut vault_lamports = vault_pre;
+        let mut recipient_lamports = 0u64;
+
+        // Outer withdraw(100) reaches its hook CPI before debiting. The
+        // hook re-enters with withdraw(1_100) — more than the balance.
+        // The inner transfer moves real lamports first...
+        vault_lamports -= 1_100;
+        recipient_lamports += 1_100;
+        // ...and the inner debit clamps instead of underflowing or erroring.
+        vault.balance = vault.balance.saturating_sub(1_100);
+        assert_eq!(vault.balance, 0);
+
+        // The outer call resumes none the wiser: its own transfer and its
+        // stale debit (computed from the 1_000 it deserialized at entry).
+        vault_lamports -= 100;
+        recipient_lamports += 100;
+        vault.balance = 1_000u64.saturating_sub(100);
+
+        // 1_200 real lamports reached the recipient; the books only ever
+        // recorded a 100-lamport decrease. The clamp prevented the
+        // underflow and nothing else.
+        test_utils::assert_lamports_moved(
+            (vault_pre, vault_lamports),
+            (0, recipient_lamports),
+            1_200,
+        );
+        assert_eq!(vault.balance, 900);
+
+        // The fix never gets this far: the outer withdraw holds the lock
+        // when the hook runs, so the nested withdraw(1_100) dies at the
+        // guard — before its transfer, before any arithmetic, clamping or
+        // otherwise.
+        let mut lock = false;
+        common::ReentrancyGuard::enter(&mut lock).unwrap(); // outer takes it
+        assert!(common::ReentrancyGuard::enter(&mut lock).is_err()); // re-entry refused
+    }
+
+    #[test]
+    fn drained_lamports_exceed_the_recorded_balance_decrease() {
+        // Lamport balances around the exploited withdraw: the nested call
+        // moves 500 and the outer call moves another 100, so 600 lamports
+        // leave the vault and land on the recipient.
+        let vault_lamports = (10_000u64, 9_400);
+        let recipient_lamports = (0u64, 600);
+        test_utils::assert_lamports_moved(vault_lamports, recipient_lamports, 600);
+
+        // The stale outer write only records the outer withdraw, so the
+        // `balance` field decreases by 100. Recipient gaining more than the
+        // bookkeeping decrease is the drain signature.
+        let balance_field_decrease = 1_000u64 - 900;
+        let recipient_gain = recipient_lamports.1 - recipient_lamports.0;
+        assert!(recipient_gain > balance_field_decrease);
     }
 }
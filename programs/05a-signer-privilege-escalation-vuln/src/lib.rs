@@ -25,7 +25,22 @@ pub mod signer_privilege_vuln {
         // Because there is no check, ANY user who signs the transaction 
         // can reach this line and modify the global protocol state.
         settings.paused = !settings.paused;
-        
+
+        Ok(())
+    }
+
+    /// VULNERABILITY: Policy says lifting the pause in an emergency needs
+    /// TWO approvals — the requesting user and the operations backend.
+    /// The accounts struct only enforces one of them.
+    pub fn emergency_unpause(ctx: Context<EmergencyUnpauseVuln>) -> Result<()> {
+        // The backend account is listed below, so the developer assumed
+        // its presence means its approval. Presence is free: any caller
+        // can reference any public key WITHOUT its signature.
+        ctx.accounts.settings.paused = false;
+
+        msg!("Emergency unpause by {} (backend: {})",
+            ctx.accounts.user.key(),
+            ctx.accounts.backend.key());
         Ok(())
     }
 }
@@ -41,12 +56,122 @@ pub struct TogglePauseVuln<'info> {
     // An attacker simply provides their own wallet here and signs.
     // Since the program doesn't link 'anyone' to 'settings.owner', 
     // the identity of the signer is effectively ignored.
-    pub anyone: Signer<'info>, 
+    pub anyone: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct EmergencyUnpauseVuln<'info> {
+    #[account(mut)]
+    pub settings: Account<'info, Settings>,
+
+    /// The requesting user — correctly typed, so this signature IS checked.
+    pub user: Signer<'info>,
+
+    /// CHECK: VULNERABILITY — the required co-signer, declared as a bare
+    /// `AccountInfo`. Anchor checks nothing about it: the transaction
+    /// carries the backend's ADDRESS but needs none of its authority. The
+    /// two-approval policy quietly became a one-approval policy.
+    pub backend: AccountInfo<'info>,
+}
+
+/// Self-description for generated docs, rendered through
+/// [`common::VulnInfo`].
+pub const VULN_INFO: common::VulnInfo = common::VulnInfo {
+    name: "signer-privilege-escalation",
+    severity: "critical",
+    summary: "set_paused demands a signature but never compares the signer to \
+the owner stored in Settings, so any keypair can flip the protocol-wide pause \
+flag -- privilege comes from merely having signed, not from being authorized.",
+    mitigation: "The fix binds the signer to Settings.owner with has_one, so \
+only the recorded owner's signature can toggle the pause.",
+};
+
+/// Renders [`VULN_INFO`] as generated documentation.
+pub fn describe() -> String {
+    VULN_INFO.describe()
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use anchor_lang::solana_program::account_info::AccountInfo;
+    use anchor_lang::solana_program::clock::Epoch;
+    use anchor_lang::{AnchorSerialize, Discriminator};
+
+    fn make_account(
+        key: Pubkey,
+        owner: Pubkey,
+        is_signer: bool,
+        is_writable: bool,
+        data: Vec<u8>,
+    ) -> AccountInfo<'static> {
+        let leaked_key = Box::leak(Box::new(key));
+        let leaked_owner = Box::leak(Box::new(owner));
+        let lamports = Box::leak(Box::new(1_000_000u64));
+        let data: &'static mut [u8] = Box::leak(data.into_boxed_slice());
+
+        AccountInfo::new(
+            leaked_key,
+            is_signer,
+            is_writable,
+            lamports,
+            data,
+            leaked_owner,
+            false,
+            Epoch::default(),
+        )
+    }
+
+    fn serialize_settings(owner: Pubkey, paused: bool) -> Vec<u8> {
+        let mut data = <Settings as Discriminator>::DISCRIMINATOR.to_vec();
+        let state = Settings { owner, paused };
+        data.extend_from_slice(&state.try_to_vec().unwrap());
+        data
+    }
+
+    /// The unenforced co-signature: the backend account arrives WITHOUT
+    /// having signed, and the handler runs to completion anyway. The same
+    /// accounts die at validation in the fix crate, where `backend` is
+    /// typed `Signer`.
+    #[test]
+    fn vuln_emergency_unpause_proceeds_without_the_backend_signature() {
+        let program_id = crate::id();
+
+        let settings_ai = Box::leak(Box::new(make_account(
+            Pubkey::new_unique(),
+            program_id,
+            false,
+            true,
+            serialize_settings(Pubkey::new_unique(), true),
+        )));
+        let user_ai = Box::leak(Box::new(make_account(
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            true,
+            false,
+            vec![],
+        )));
+        // The backend's address, minus the backend's approval.
+        let backend_ai = Box::leak(Box::new(make_account(
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            false,
+            false,
+            vec![],
+        )));
+        assert!(!backend_ai.is_signer);
+
+        let mut accounts = EmergencyUnpauseVuln {
+            settings: Account::try_from(&*settings_ai).unwrap(),
+            user: Signer::try_from(&*user_ai).unwrap(),
+            backend: (*backend_ai).clone(),
+        };
+        let ctx = Context::new(&program_id, &mut accounts, &[], EmergencyUnpauseVulnBumps {});
+        signer_privilege_vuln::emergency_unpause(ctx).unwrap();
+
+        // One signature bought a two-signature action.
+        assert!(!accounts.settings.paused);
+    }
 
     #[test]
     fn vuln_allows_any_signer_to_toggle() {
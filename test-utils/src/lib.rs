@@ -0,0 +1,1034 @@
+//! Shared helpers for the unit tests and Pinocchio-style simulations in this
+//! workspace. Nothing in here is deployed on-chain; these utilities exist so
+//! every example can express the same testing ideas (rollback semantics,
+//! account construction, state inspection) without re-implementing them.
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::account_info::AccountInfo;
+use anchor_lang::solana_program::clock::Epoch;
+
+/// Builds a `'static` `AccountInfo` at the canonical PDA for `seeds` under
+/// `program_id`, owned by that program, and returns it together with the
+/// bump. Tests across the workspace otherwise repeat the same
+/// `find_program_address` + `Box::leak` dance by hand.
+///
+/// The account is writable and not a signer — the shape of every PDA state
+/// account these tests exercise. The backing allocations are intentionally
+/// leaked so the `AccountInfo` can outlive the helper, which is fine for
+/// test processes.
+pub fn make_pda_account(
+    program_id: &Pubkey,
+    seeds: &[&[u8]],
+    data: Vec<u8>,
+) -> (AccountInfo<'static>, u8) {
+    let (key, bump) = Pubkey::find_program_address(seeds, program_id);
+
+    let leaked_key = Box::leak(Box::new(key));
+    let leaked_owner = Box::leak(Box::new(*program_id));
+    let lamports = Box::leak(Box::new(1_000_000_000u64));
+    let data: &'static mut [u8] = Box::leak(data.into_boxed_slice());
+
+    let info = AccountInfo::new(
+        leaked_key,
+        false,
+        true,
+        lamports,
+        data,
+        leaked_owner,
+        false,
+        Epoch::default(),
+    );
+    (info, bump)
+}
+
+/// Derives a deterministic test pubkey from `seed`. `Pubkey::new_unique()`
+/// hands out a different key every run, which makes a failing test's log
+/// useless for the next run; keys minted here are stable, so assertions can
+/// name them and reruns reproduce the exact same account graph.
+pub fn seeded_pubkey(seed: u64) -> Pubkey {
+    // Piggyback on the PDA derivation (the one deterministic key-stretching
+    // primitive already in scope) with a fixed namespace program.
+    Pubkey::find_program_address(
+        &[b"test-utils:seeded", &seed.to_le_bytes()],
+        &Pubkey::default(),
+    )
+    .0
+}
+
+/// `make_pda_account`'s sibling for non-PDA accounts: a `'static`, writable,
+/// non-signing `AccountInfo` whose address is `seeded_pubkey(seed)` — the
+/// same `seed` always yields the same account key. Backing allocations are
+/// leaked for `'static` lifetime, as with the other constructors here.
+pub fn make_account_seeded(seed: u64, owner: &Pubkey, data: Vec<u8>) -> AccountInfo<'static> {
+    let leaked_key = Box::leak(Box::new(seeded_pubkey(seed)));
+    let leaked_owner = Box::leak(Box::new(*owner));
+    let lamports = Box::leak(Box::new(1_000_000_000u64));
+    let data: &'static mut [u8] = Box::leak(data.into_boxed_slice());
+
+    AccountInfo::new(
+        leaked_key,
+        false,
+        true,
+        lamports,
+        data,
+        leaked_owner,
+        false,
+        Epoch::default(),
+    )
+}
+
+/// Builds the `&[&[&[u8]]]` signer-seeds slice an `invoke_signed` call
+/// expects for a `[seed_prefix, authority, bump]` PDA — the shape both the
+/// reentrancy and escrow examples sign their vault CPIs with.
+///
+/// The nested borrows make this painful to assemble inline (three levels of
+/// slices, each wanting a stable owner), so the helper leaks the backing
+/// allocations the same way `make_pda_account` does and hands back a
+/// `'static` view that can be passed straight to `invoke_signed`.
+pub fn pda_signer_seeds(
+    seed_prefix: &[u8],
+    authority: &Pubkey,
+    bump: u8,
+) -> &'static [&'static [&'static [u8]]] {
+    let seeds: Vec<&'static [u8]> = vec![
+        Box::leak(seed_prefix.to_vec().into_boxed_slice()),
+        Box::leak(authority.to_bytes().to_vec().into_boxed_slice()),
+        Box::leak(vec![bump].into_boxed_slice()),
+    ];
+    let seeds: &'static [&'static [u8]] = Box::leak(seeds.into_boxed_slice());
+    Box::leak(vec![seeds].into_boxed_slice())
+}
+
+/// Runs `f` against `state`, emulating Solana's transaction semantics:
+/// if `f` returns `Err`, every change it made to `state` is rolled back,
+/// exactly like a failed transaction reverting all account writes.
+///
+/// The Pinocchio `safe_withdraw` simulation previously restored its
+/// `original_balance` by hand; this helper generalizes that pattern so any
+/// cloneable state struct gets the same snapshot-and-restore behavior.
+pub fn with_rollback<T, E, F>(state: &mut T, f: F) -> Result<(), E>
+where
+    T: Clone,
+    F: FnOnce(&mut T) -> Result<(), E>,
+{
+    // Snapshot before the "transaction" body runs.
+    let snapshot = state.clone();
+
+    let result = f(state);
+
+    // On failure, restore the snapshot — partial writes must not survive,
+    // just as a failed Solana transaction leaves no account changes behind.
+    if result.is_err() {
+        *state = snapshot;
+    }
+
+    result
+}
+
+/// Reports every byte offset at which `after` differs from `before`, as
+/// `(offset, before_byte, after_byte)` triples in offset order.
+///
+/// Overwrite-attack tests use this to prove precisely which fields an
+/// exploit corrupted — "only the leading message bytes changed" is a much
+/// stronger claim than "the data is different". If the buffers have
+/// different lengths, the tail of the longer one is reported against `0`,
+/// matching how a reallocated account's fresh bytes read.
+pub fn diff_bytes(before: &[u8], after: &[u8]) -> Vec<(usize, u8, u8)> {
+    let len = before.len().max(after.len());
+    (0..len)
+        .filter_map(|i| {
+            let b = before.get(i).copied().unwrap_or(0);
+            let a = after.get(i).copied().unwrap_or(0);
+            (b != a).then_some((i, b, a))
+        })
+        .collect()
+}
+
+/// Asserts that a write landed cleanly: `buf[offset..]` starts with exactly
+/// `expected`, and every byte after the written region is zero.
+///
+/// This catches partial-overwrite bugs, where a short value is copied over a
+/// longer old one and the old value's tail survives past the new write. A
+/// self-describing serialization (discriminator, length prefix, fields)
+/// passes against a zeroed buffer; a raw `copy_from_slice` of just the new
+/// bytes fails on the first stale byte it left behind.
+///
+/// # Panics
+///
+/// Panics if the region doesn't fit in `buf`, if the written bytes differ
+/// from `expected`, or if any byte after the region is non-zero.
+pub fn assert_clean_write(buf: &[u8], offset: usize, expected: &[u8]) {
+    let end = offset + expected.len();
+    assert!(
+        end <= buf.len(),
+        "written region {offset}..{end} does not fit in a {} byte buffer",
+        buf.len()
+    );
+    assert_eq!(
+        &buf[offset..end],
+        expected,
+        "bytes at {offset}..{end} do not match the expected write"
+    );
+    if let Some((i, byte)) = buf[end..].iter().enumerate().find(|(_, b)| **b != 0) {
+        panic!(
+            "stale byte 0x{byte:02x} at offset {} after the written region",
+            end + i
+        );
+    }
+}
+
+/// Asserts that exactly `expected` lamports moved from one account to the
+/// other, given `(pre, post)` balance pairs captured around the call under
+/// test.
+///
+/// Deterministic on purpose: both sides must match, so a test that drains
+/// more than it debits (the re-entrancy drain signature) fails loudly
+/// instead of only checking the recipient side.
+///
+/// # Panics
+///
+/// Panics with a balance-diff message if either side moved a different
+/// amount than `expected`.
+pub fn assert_lamports_moved(from: (u64, u64), to: (u64, u64), expected: u64) {
+    let (from_pre, from_post) = from;
+    let (to_pre, to_post) = to;
+
+    let sent = from_pre
+        .checked_sub(from_post)
+        .expect("sender balance increased; expected it to decrease");
+    let received = to_post
+        .checked_sub(to_pre)
+        .expect("recipient balance decreased; expected it to increase");
+
+    assert_eq!(
+        sent, expected,
+        "sender lost {sent} lamports, expected {expected}"
+    );
+    assert_eq!(
+        received, expected,
+        "recipient gained {received} lamports, expected {expected}"
+    );
+}
+
+/// Asserts that running an operation a second time changes nothing: `run`
+/// performs the operation and returns a snapshot of the observable state
+/// afterwards, and the helper compares the snapshots from two consecutive
+/// runs.
+///
+/// This is the replay-protection check. A fix that gates on a version
+/// field, a nonce, or an already-applied flag either no-ops or rejects the
+/// second run — both leave the snapshot unchanged, and both count as
+/// idempotent here. What matters is that the replayed transaction cannot
+/// have an effect.
+///
+/// # Panics
+///
+/// Panics if the second run's snapshot differs from the first's.
+pub fn assert_idempotent<S, F>(mut run: F)
+where
+    S: PartialEq + core::fmt::Debug,
+    F: FnMut() -> S,
+{
+    let first = run();
+    let second = run();
+    assert_eq!(
+        second, first,
+        "second run changed observable state; the operation is replayable"
+    );
+}
+
+/// The inverse of [`assert_idempotent`]: proves an operation IS replayable
+/// by checking the second run moves the observable state again. Vuln tests
+/// use this to pin down the missing replay protection — a duplicate admin
+/// appended twice, a balance debited twice — so the fix's idempotence
+/// claim has a demonstrated attack to stand against.
+///
+/// # Panics
+///
+/// Panics if the second run left the snapshot unchanged.
+pub fn assert_not_idempotent<S, F>(mut run: F)
+where
+    S: PartialEq + core::fmt::Debug,
+    F: FnMut() -> S,
+{
+    let first = run();
+    let second = run();
+    assert_ne!(
+        second, first,
+        "second run had no effect; the operation is idempotent"
+    );
+}
+
+/// Reports whether two account types' Borsh layouts collide: a default
+/// instance of either serializes to bytes the other deserializes cleanly,
+/// discriminators excluded. A collision is the precondition for the
+/// type-confusion attacks in this workspace — when it holds, only the
+/// 8-byte discriminator distinguishes the types on-chain, and any victim
+/// that skips that check will happily misread one as the other.
+///
+/// Borsh's `try_from_slice` insists every byte is consumed, so differing
+/// fixed sizes (or incompatible variable-length fields) report `false`.
+pub fn layouts_collide<A, B>() -> bool
+where
+    A: anchor_lang::AnchorSerialize + anchor_lang::AnchorDeserialize + Default,
+    B: anchor_lang::AnchorSerialize + anchor_lang::AnchorDeserialize + Default,
+{
+    let a_bytes = match A::default().try_to_vec() {
+        Ok(bytes) => bytes,
+        Err(_) => return false,
+    };
+    let b_bytes = match B::default().try_to_vec() {
+        Ok(bytes) => bytes,
+        Err(_) => return false,
+    };
+    B::try_from_slice(&a_bytes).is_ok() && A::try_from_slice(&b_bytes).is_ok()
+}
+
+/// Flat, JSON-ready summary of one attack attempt: the on-chain
+/// [`AttackLog`]'s fields joined with metadata about the vulnerability that
+/// was exercised. A CLI serializes a batch of these (via serde) so external
+/// dashboards ingest test runs without knowing any Anchor account layout.
+///
+/// Pubkeys are rendered as base58 strings and the attack type as its
+/// variant name — the JSON is for humans and dashboards, not for replay.
+///
+/// [`AttackLog`]: missing_account_attacker::AttackLog
+#[derive(serde::Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct AttackReport {
+    pub attacker: String,
+    pub target: String,
+    pub attack_type: String,
+    pub succeeded: bool,
+    pub timestamp: i64,
+    /// Short label of the vulnerability under test, e.g. "missing-account-validation".
+    pub vuln_name: String,
+    /// Base58 id of the vulnerable program the attack was aimed at.
+    pub vuln_program: String,
+}
+
+/// The vuln-side metadata an [`AttackReport`] carries alongside the log.
+pub struct VulnMetadata {
+    pub name: &'static str,
+    pub program: Pubkey,
+}
+
+impl AttackReport {
+    /// Joins a recorded [`AttackLog`] with the vulnerability metadata the
+    /// harness already knows, producing one self-contained report row.
+    ///
+    /// [`AttackLog`]: missing_account_attacker::AttackLog
+    pub fn from_log(log: &missing_account_attacker::AttackLog, info: &VulnMetadata) -> Self {
+        use missing_account_attacker::AttackType;
+
+        let attack_type = match log.attack_type {
+            AttackType::None => "None",
+            AttackType::AccountSubstitution => "AccountSubstitution",
+            AttackType::OwnershipSpoofing => "OwnershipSpoofing",
+            AttackType::PdaBypass => "PdaBypass",
+            AttackType::AuthorityEscalation => "AuthorityEscalation",
+            AttackType::TypeConfusion => "TypeConfusion",
+            AttackType::DenialOfService => "DenialOfService",
+        };
+
+        Self {
+            attacker: log.attacker.to_string(),
+            target: log.target.to_string(),
+            attack_type: attack_type.to_string(),
+            succeeded: log.succeeded,
+            timestamp: log.timestamp,
+            vuln_name: info.name.to_string(),
+            vuln_program: info.program.to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Clone, Debug, PartialEq)]
+    struct VaultState {
+        balance: u64,
+        is_locked: bool,
+    }
+
+    #[test]
+    fn attack_report_serializes_the_log_and_metadata_to_json() {
+        let attacker = seeded_pubkey(1);
+        let log = missing_account_attacker::AttackLog {
+            attacker,
+            target: seeded_pubkey(2),
+            attack_type: missing_account_attacker::AttackType::TypeConfusion,
+            succeeded: true,
+            timestamp: 1_700_000_000,
+        };
+        let info = VulnMetadata {
+            name: "missing-account-validation",
+            program: seeded_pubkey(3),
+        };
+
+        let report = AttackReport::from_log(&log, &info);
+        let json = serde_json::to_string(&report).unwrap();
+
+        // The dashboard-facing fields are present and human-readable.
+        assert!(json.contains(&attacker.to_string()));
+        assert!(json.contains("\"succeeded\":true"));
+        assert!(json.contains("\"attack_type\":\"TypeConfusion\""));
+        assert!(json.contains("\"vuln_name\":\"missing-account-validation\""));
+        assert!(json.contains(&seeded_pubkey(3).to_string()));
+    }
+
+    #[test]
+    fn seeded_keys_are_stable_and_distinct() {
+        // Same seed, same key — across calls and across runs (the derivation
+        // has no process-local state to drift on).
+        assert_eq!(seeded_pubkey(7), seeded_pubkey(7));
+        assert_ne!(seeded_pubkey(7), seeded_pubkey(8));
+
+        // The account constructor mints its address from the same seed.
+        let owner = seeded_pubkey(0);
+        let account = make_account_seeded(7, &owner, vec![0u8; 16]);
+        assert_eq!(*account.key, seeded_pubkey(7));
+        assert_eq!(*account.owner, owner);
+    }
+
+    /// The collision detector agrees with the workspace's reality: the two
+    /// types the type-confusion attack swaps really do share a layout, and
+    /// a pair with different footprints (41 vs 37 bytes) does not.
+    #[test]
+    fn layout_collision_detector_matches_the_type_confusion_pairs() {
+        // TreasuryConfig {admin: Pubkey, fee_bps: u16} vs
+        // UserProfile {wallet: Pubkey, level: u16}: byte-for-byte twins.
+        assert!(layouts_collide::<
+            missing_account_attacker::TreasuryConfig,
+            missing_account_attacker::UserProfile,
+        >());
+
+        // Vault and Config serialize to different lengths, so Borsh's
+        // consume-every-byte rule refuses the cross-read in both directions.
+        assert!(!layouts_collide::<
+            unsafe_arithmetic_fix::Vault,
+            incorrect_authority_fix::Config,
+        >());
+    }
+
+    /// The two idempotency assertions against the simplest possible
+    /// operations: an append visibly replays, an overwrite of the same
+    /// value does not, and a gated operation that refuses its second run
+    /// counts as idempotent — the gate IS the replay protection.
+    #[test]
+    fn idempotency_helpers_classify_trivial_operations() {
+        // Appending replays: each run grows the list again.
+        let mut admins: Vec<u64> = vec![];
+        assert_not_idempotent(|| {
+            admins.push(42);
+            admins.len()
+        });
+
+        // Overwriting with the same value does not.
+        let mut fee = 0u16;
+        assert_idempotent(|| {
+            fee = 100;
+            fee
+        });
+
+        // A version-gated operation: the first run applies and bumps the
+        // version, the second is refused, so the snapshot holds still.
+        let mut state = (1u8, 0u64); // (version, value)
+        assert_idempotent(|| {
+            if state.0 == 1 {
+                state = (2, 7_777);
+            }
+            state
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "the operation is replayable")]
+    fn idempotent_assertion_catches_a_replayable_operation() {
+        let mut counter = 0u64;
+        assert_idempotent(|| {
+            counter += 1;
+            counter
+        });
+    }
+
+    #[test]
+    fn rollback_restores_state_on_err() {
+        let mut state = VaultState {
+            balance: 1_000,
+            is_locked: false,
+        };
+
+        let err = with_rollback(&mut state, |s| {
+            // Mutate both fields, then fail mid-"transaction".
+            s.balance = 0;
+            s.is_locked = true;
+            Err::<(), &str>("insufficient funds")
+        })
+        .unwrap_err();
+
+        assert_eq!(err, "insufficient funds");
+        // Every write is rolled back, not just the last one.
+        assert_eq!(
+            state,
+            VaultState {
+                balance: 1_000,
+                is_locked: false,
+            }
+        );
+    }
+
+    #[test]
+    fn rollback_commits_state_on_ok() {
+        let mut state = VaultState {
+            balance: 1_000,
+            is_locked: false,
+        };
+
+        with_rollback(&mut state, |s| {
+            s.balance -= 100;
+            Ok::<(), &str>(())
+        })
+        .unwrap();
+
+        assert_eq!(state.balance, 900);
+        assert!(!state.is_locked);
+    }
+
+    #[test]
+    fn pda_account_sits_at_the_canonical_address() {
+        let program_id = Pubkey::new_unique();
+        let authority = Pubkey::new_unique();
+        let seeds: &[&[u8]] = &[b"message", authority.as_ref()];
+
+        let (info, bump) = make_pda_account(&program_id, seeds, vec![0u8; 16]);
+
+        let (expected_key, expected_bump) = Pubkey::find_program_address(seeds, &program_id);
+        assert_eq!(*info.key, expected_key);
+        assert_eq!(bump, expected_bump);
+        assert_eq!(*info.owner, program_id);
+        assert_eq!(info.data_len(), 16);
+        assert!(info.is_writable);
+        assert!(!info.is_signer);
+    }
+
+    /// The seeds the helper assembles must be exactly what the runtime
+    /// verifies during `invoke_signed`: prefix, authority bytes, then the
+    /// bump, re-deriving the canonical PDA.
+    #[test]
+    fn signer_seeds_re_derive_the_canonical_pda() {
+        let program_id = Pubkey::new_unique();
+        let authority = Pubkey::new_unique();
+        let (expected_key, bump) =
+            Pubkey::find_program_address(&[b"vault", authority.as_ref()], &program_id);
+
+        let signer_seeds = pda_signer_seeds(b"vault", &authority, bump);
+
+        // One signing PDA, whose seeds resolve to the canonical address.
+        assert_eq!(signer_seeds.len(), 1);
+        let derived = Pubkey::create_program_address(signer_seeds[0], &program_id).unwrap();
+        assert_eq!(derived, expected_key);
+
+        // A wrong bump must not silently resolve to the same address.
+        let tampered = pda_signer_seeds(b"vault", &authority, bump.wrapping_sub(1));
+        if let Ok(other) = Pubkey::create_program_address(tampered[0], &program_id) {
+            assert_ne!(other, expected_key);
+        }
+    }
+
+    #[test]
+    fn diff_bytes_pinpoints_changed_offsets() {
+        let before = [0u8, 1, 2, 3, 4];
+        let after = [0u8, 9, 2, 3, 7];
+
+        assert_eq!(diff_bytes(&before, &after), vec![(1, 1, 9), (4, 4, 7)]);
+        // Identical buffers diff to nothing.
+        assert_eq!(diff_bytes(&before, &before), vec![]);
+    }
+
+    #[test]
+    fn diff_bytes_reports_length_changes_against_zero() {
+        // A grown buffer's new bytes show up as 0 -> value...
+        assert_eq!(diff_bytes(&[1, 2], &[1, 2, 5]), vec![(2, 0, 5)]);
+        // ...and truncated bytes as value -> 0.
+        assert_eq!(diff_bytes(&[1, 2, 5], &[1, 2]), vec![(2, 5, 0)]);
+    }
+
+    #[test]
+    fn clean_write_accepts_exact_region_with_zeroed_tail() {
+        let mut buf = vec![0u8; 16];
+        buf[4..9].copy_from_slice(b"hello");
+        assert_clean_write(&buf, 4, b"hello");
+    }
+
+    #[test]
+    #[should_panic(expected = "stale byte")]
+    fn clean_write_catches_surviving_tail_bytes() {
+        // "hi" written over a longer old value: "llo" survives past the end.
+        let mut buf = *b"hello";
+        buf[..2].copy_from_slice(b"hi");
+        assert_clean_write(&buf, 0, b"hi");
+    }
+
+    #[test]
+    #[should_panic(expected = "do not match")]
+    fn clean_write_catches_mismatched_region() {
+        assert_clean_write(&[1, 2, 3, 0], 0, &[1, 9, 3]);
+    }
+
+    #[test]
+    fn lamports_moved_accepts_matched_transfer() {
+        // 300 lamports leave the vault and arrive at the recipient.
+        assert_lamports_moved((1_000, 700), (50, 350), 300);
+    }
+
+    #[test]
+    #[should_panic(expected = "recipient gained")]
+    fn lamports_moved_catches_over_credited_recipient() {
+        // Recipient gained 600 while the sender only lost 300 — the kind of
+        // mismatch a drain produces. The helper must flag it.
+        assert_lamports_moved((1_000, 700), (50, 650), 300);
+    }
+
+    #[test]
+    #[should_panic(expected = "sender lost")]
+    fn lamports_moved_catches_short_debit() {
+        assert_lamports_moved((1_000, 900), (50, 350), 300);
+    }
+
+    /// The whole point of `common::Exploit`: one driver loop runs every
+    /// attacker in the workspace. Each context below is the post-attack
+    /// image the matching VULN program leaves behind, so every registered
+    /// exploit must judge itself as having landed. (The test lives here
+    /// rather than in `common` because a dev-dependency from `common` back
+    /// onto the attacker crates would build two instances of the trait.)
+    #[test]
+    fn registered_exploits_all_land_against_their_vuln_targets() {
+        use anchor_lang::{AnchorSerialize, Discriminator};
+        use common::{Exploit, ExploitCtx, SuccessOracle};
+
+        // Every registered exploit doubles as its own post-state oracle, so
+        // the matrix can box one value and ask it both questions.
+        trait Registered: Exploit + SuccessOracle {}
+        impl<T: Exploit + SuccessOracle> Registered for T {}
+
+        fn serialize<T: AnchorSerialize + Discriminator>(state: &T) -> Vec<u8> {
+            let mut data = T::DISCRIMINATOR.to_vec();
+            data.extend_from_slice(&state.try_to_vec().unwrap());
+            data
+        }
+
+        let attacker = Pubkey::new_unique();
+        let victim = Pubkey::new_unique();
+
+        // 01: a forged UserProfile sits where a TreasuryConfig is expected.
+        let forged_profile = serialize(&missing_account_attacker::UserProfile {
+            wallet: attacker,
+            level: 7,
+        });
+        // 02: the config carries the fee a non-admin pushed in.
+        let overridden_config = serialize(&incorrect_authority_fix::Config {
+            admin: victim,
+            fee_bps: 9_999,
+            version: incorrect_authority_fix::CONFIG_VERSION,
+            significant_change_bps: 0,
+            treasury: victim,
+        });
+        // 03: a 142-lamport withdraw from a 100-lamport vault wrapped.
+        let wrapped_vault = serialize(&unsafe_arithmetic_fix::Vault {
+            frozen: false,
+            balance: u64::MAX - 41,
+            owner: victim,
+        });
+        // 05: the protocol is paused and the attacker is not the owner.
+        let paused_settings = serialize(&signer_privilege_fix::Settings {
+            owner: victim,
+            paused: true,
+        });
+
+        let quiet_lamports = (1_000u64, 1_000);
+        let cases: Vec<(Box<dyn Registered>, ExploitCtx)> = vec![
+            (
+                Box::new(missing_account_attacker::TypeConfusionExploit),
+                ExploitCtx {
+                    target_data: &forged_profile,
+                    target_lamports: quiet_lamports,
+                    attacker,
+                },
+            ),
+            (
+                Box::new(incorrect_authority_attacker::FeeOverrideExploit {
+                    malicious_fee: 9_999,
+                }),
+                ExploitCtx {
+                    target_data: &overridden_config,
+                    target_lamports: quiet_lamports,
+                    attacker,
+                },
+            ),
+            (
+                Box::new(unsafe_arithmetic_attacker::UnderflowExploit { pre_balance: 100 }),
+                ExploitCtx {
+                    target_data: &wrapped_vault,
+                    target_lamports: quiet_lamports,
+                    attacker,
+                },
+            ),
+            // 04: 600 lamports left the vault but the stale write only
+            // booked the outer 100 — this judgement runs on lamports alone.
+            (
+                Box::new(cpi_reentrancy_attacker::DrainExploit {
+                    recorded_decrease: 100,
+                }),
+                ExploitCtx {
+                    target_data: &[],
+                    target_lamports: (10_000, 9_400),
+                    attacker,
+                },
+            ),
+            (
+                Box::new(signer_privilege_attacker::EscalationExploit),
+                ExploitCtx {
+                    target_data: &paused_settings,
+                    target_lamports: quiet_lamports,
+                    attacker,
+                },
+            ),
+        ];
+
+        for (exploit, ctx) in &cases {
+            // The oracle's verdict comes from the victim account's post-
+            // state, never from the attacker's self-reported flag.
+            assert!(
+                exploit.succeeded(ctx),
+                "{} oracle should judge the vuln post-state as exploited",
+                exploit.name()
+            );
+
+            // The narrated outcome must tell the same story as the oracle.
+            let outcome = exploit.run(ctx).unwrap();
+            assert!(
+                outcome.exploited,
+                "{} should land against its vuln target: {}",
+                exploit.name(),
+                outcome.detail
+            );
+        }
+    }
+
+    /// The oracles' other half: handed post-state the FIX programs leave
+    /// behind — untouched fee, unpaused settings, a balance that only went
+    /// down, lamports matching the books — every one must answer "blocked".
+    /// A matrix that asked the attackers' own `succeeded` flags instead
+    /// would pass here too, for exactly the wrong reason.
+    #[test]
+    fn success_oracles_reject_untouched_post_state() {
+        use anchor_lang::{AnchorSerialize, Discriminator};
+        use common::{ExploitCtx, SuccessOracle};
+
+        fn serialize<T: AnchorSerialize + Discriminator>(state: &T) -> Vec<u8> {
+            let mut data = T::DISCRIMINATOR.to_vec();
+            data.extend_from_slice(&state.try_to_vec().unwrap());
+            data
+        }
+
+        let attacker = Pubkey::new_unique();
+        let victim = Pubkey::new_unique();
+
+        // 01: a genuine TreasuryConfig whose admin is not the attacker.
+        let honest_config = serialize(&missing_account_attacker::TreasuryConfig {
+            admin: victim,
+            fee_bps: 250,
+        });
+        // 02: the fee the admin set, not the one the attacker wanted.
+        let honest_fee = serialize(&incorrect_authority_fix::Config {
+            admin: victim,
+            fee_bps: 250,
+            version: incorrect_authority_fix::CONFIG_VERSION,
+            significant_change_bps: 0,
+            treasury: victim,
+        });
+        // 03: a withdrawal that debited normally.
+        let debited_vault = serialize(&unsafe_arithmetic_fix::Vault {
+            frozen: false,
+            balance: 58,
+            owner: victim,
+        });
+        // 05: still unpaused.
+        let calm_settings = serialize(&signer_privilege_fix::Settings {
+            owner: victim,
+            paused: false,
+        });
+
+        let quiet_lamports = (1_000u64, 1_000);
+        let cases: Vec<(&str, Box<dyn SuccessOracle>, ExploitCtx)> = vec![
+            (
+                "type-confusion",
+                Box::new(missing_account_attacker::TypeConfusionExploit),
+                ExploitCtx {
+                    target_data: &honest_config,
+                    target_lamports: quiet_lamports,
+                    attacker,
+                },
+            ),
+            (
+                "authority-escalation",
+                Box::new(incorrect_authority_attacker::FeeOverrideExploit {
+                    malicious_fee: 9_999,
+                }),
+                ExploitCtx {
+                    target_data: &honest_fee,
+                    target_lamports: quiet_lamports,
+                    attacker,
+                },
+            ),
+            (
+                "arithmetic-underflow",
+                Box::new(unsafe_arithmetic_attacker::UnderflowExploit { pre_balance: 100 }),
+                ExploitCtx {
+                    target_data: &debited_vault,
+                    target_lamports: quiet_lamports,
+                    attacker,
+                },
+            ),
+            // 04: 100 lamports left and exactly 100 were booked.
+            (
+                "cpi-reentrancy-drain",
+                Box::new(cpi_reentrancy_attacker::DrainExploit {
+                    recorded_decrease: 100,
+                }),
+                ExploitCtx {
+                    target_data: &[],
+                    target_lamports: (10_000, 9_900),
+                    attacker,
+                },
+            ),
+            (
+                "signer-privilege-escalation",
+                Box::new(signer_privilege_attacker::EscalationExploit),
+                ExploitCtx {
+                    target_data: &calm_settings,
+                    target_lamports: quiet_lamports,
+                    attacker,
+                },
+            ),
+        ];
+
+        for (name, oracle, ctx) in &cases {
+            assert!(
+                !oracle.succeeded(ctx),
+                "{} oracle must not claim success over untouched post-state",
+                name
+            );
+        }
+    }
+
+    /// Bug composition: one vulnerability forges the state a later check
+    /// trusts. Step one drives the arithmetic vuln's sign-confusion path —
+    /// "withdrawing" a negative amount wraps the attacker's balance UP by a
+    /// million. Step two presents that inflated vault to the FIX's own
+    /// withdraw, whose sufficiency gate ("the balance authorizes the debit")
+    /// now approves a drain a thousand times larger than anything the
+    /// attacker deposited. Against the fixed program, step one dies at the
+    /// sign check and the chain never starts — which is why fixing the
+    /// upstream bug protects checks that were never wrong themselves.
+    #[test]
+    fn underflow_inflation_chains_into_a_downstream_sufficiency_gate() {
+        use anchor_lang::prelude::{Account, Context, Signer};
+        use anchor_lang::solana_program::account_info::AccountInfo;
+        use anchor_lang::solana_program::clock::Epoch;
+        use anchor_lang::{AnchorSerialize, Discriminator};
+
+        fn make_account(
+            key: Pubkey,
+            owner: Pubkey,
+            is_signer: bool,
+            is_writable: bool,
+            data: Vec<u8>,
+        ) -> &'static AccountInfo<'static> {
+            Box::leak(Box::new(AccountInfo::new(
+                Box::leak(Box::new(key)),
+                is_signer,
+                is_writable,
+                Box::leak(Box::new(1_000_000_000u64)),
+                Box::leak(data.into_boxed_slice()),
+                Box::leak(Box::new(owner)),
+                false,
+                Epoch::default(),
+            )))
+        }
+
+        fn serialize<T: AnchorSerialize + Discriminator>(state: &T) -> Vec<u8> {
+            let mut data = T::DISCRIMINATOR.to_vec();
+            data.extend_from_slice(&state.try_to_vec().unwrap());
+            data
+        }
+
+        let attacker = Pubkey::new_unique();
+
+        // --- Step 1 against the vuln: inflate via wrapped subtraction. ---
+        // The attacker's honest holdings: 1_000 lamports.
+        let vault_data = serialize(&unsafe_arithmetic_vuln::Vault {
+            frozen: false,
+            balance: 1_000,
+            owner: attacker,
+        });
+        let vault_ai = make_account(
+            Pubkey::new_unique(),
+            unsafe_arithmetic_vuln::ID,
+            false,
+            true,
+            vault_data,
+        );
+        let attacker_ai = make_account(attacker, Pubkey::new_unique(), true, false, vec![]);
+        let mut accounts = unsafe_arithmetic_vuln::WithdrawVuln {
+            vault: Account::try_from(vault_ai).unwrap(),
+            owner: Signer::try_from(attacker_ai).unwrap(),
+        };
+        let ctx = Context::new(
+            &unsafe_arithmetic_vuln::ID,
+            &mut accounts,
+            &[],
+            unsafe_arithmetic_vuln::WithdrawVulnBumps {},
+        );
+        unsafe_arithmetic_vuln::unsafe_arithmetic_vuln::withdraw_signed(ctx, -1_000_000).unwrap();
+        let inflated = accounts.vault.balance;
+        assert_eq!(inflated, 1_001_000, "the 'withdrawal' wrapped the balance up");
+
+        // --- Step 2: the forged balance passes the fix's own gate. ---
+        // checked_sub never wrongly approved anything; it trusts the stored
+        // balance, and the stored balance is now a lie.
+        let inflated_vault_data = serialize(&unsafe_arithmetic_fix::Vault {
+            frozen: false,
+            balance: inflated,
+            owner: attacker,
+        });
+        let vault_ai = make_account(
+            Pubkey::new_unique(),
+            unsafe_arithmetic_fix::ID,
+            false,
+            true,
+            inflated_vault_data,
+        );
+        let settings_data = serialize(&signer_privilege_fix::Settings {
+            owner: Pubkey::new_unique(),
+            paused: false,
+        });
+        let settings_ai = make_account(
+            Pubkey::new_unique(),
+            signer_privilege_fix::ID,
+            false,
+            false,
+            settings_data,
+        );
+        let mut accounts = unsafe_arithmetic_fix::WithdrawSafe {
+            vault: Account::try_from(vault_ai).unwrap(),
+            owner: Signer::try_from(attacker_ai).unwrap(),
+            settings: Account::try_from(settings_ai).unwrap(),
+        };
+        let ctx = Context::new(
+            &unsafe_arithmetic_fix::ID,
+            &mut accounts,
+            &[],
+            unsafe_arithmetic_fix::WithdrawSafeBumps {},
+        );
+        unsafe_arithmetic_fix::unsafe_arithmetic_fix::withdraw(ctx, 1_000_000).unwrap();
+        assert_eq!(accounts.vault.balance, 1_000, "a 1000x over-draw sailed through");
+
+        // --- The chain against the fixes: step 1 never happens. ---
+        let honest_vault_data = serialize(&unsafe_arithmetic_fix::Vault {
+            frozen: false,
+            balance: 1_000,
+            owner: attacker,
+        });
+        let vault_ai = make_account(
+            Pubkey::new_unique(),
+            unsafe_arithmetic_fix::ID,
+            false,
+            true,
+            honest_vault_data,
+        );
+        let settings_data = serialize(&signer_privilege_fix::Settings {
+            owner: Pubkey::new_unique(),
+            paused: false,
+        });
+        let settings_ai = make_account(
+            Pubkey::new_unique(),
+            signer_privilege_fix::ID,
+            false,
+            false,
+            settings_data,
+        );
+        let mut accounts = unsafe_arithmetic_fix::WithdrawSafe {
+            vault: Account::try_from(vault_ai).unwrap(),
+            owner: Signer::try_from(attacker_ai).unwrap(),
+            settings: Account::try_from(settings_ai).unwrap(),
+        };
+        let ctx = Context::new(
+            &unsafe_arithmetic_fix::ID,
+            &mut accounts,
+            &[],
+            unsafe_arithmetic_fix::WithdrawSafeBumps {},
+        );
+        let err = unsafe_arithmetic_fix::unsafe_arithmetic_fix::withdraw_signed(ctx, -1_000_000)
+            .unwrap_err();
+        assert!(format!("{}", err).contains("negative"));
+        // No inflation, so the big withdrawal has nothing to stand on.
+        assert_eq!(accounts.vault.balance, 1_000);
+        assert!(accounts.vault.balance.checked_sub(1_000_000).is_none());
+    }
+
+    /// The vuln/fix behavioral difference as a table: one shared set of
+    /// `(balance, amount)` inputs driven through BOTH programs' extracted
+    /// pure functions. Wherever the withdrawal is funded the two agree to
+    /// the lamport — that shared region is why the unchecked operator
+    /// survives review. Everywhere else they part ways: the fix returns
+    /// InsufficientFunds, the vuln wraps (or, under this workspace's
+    /// always-on overflow checks, panics — probed at runtime exactly as the
+    /// vuln crate's own profile test does).
+    #[test]
+    fn shared_inputs_split_the_programs_exactly_at_the_funded_boundary() {
+        let balances = [0u64, 1, 10, 999, 1_000, 1_001, u64::MAX - 1, u64::MAX];
+        let amounts = [0u64, 1, 10, 999, 1_000, 1_001, u64::MAX];
+
+        let overflow_checks_enabled =
+            std::panic::catch_unwind(|| std::hint::black_box(0u64) - std::hint::black_box(1))
+                .is_err();
+
+        for balance in balances {
+            for amount in amounts {
+                let fixed = unsafe_arithmetic_fix::apply_withdraw(balance, amount);
+
+                if amount <= balance {
+                    // Funded: full agreement, down to the exact remainder.
+                    let vuln = unsafe_arithmetic_vuln::apply_withdraw(balance, amount);
+                    assert_eq!(vuln, balance - amount);
+                    assert_eq!(fixed.unwrap(), vuln, "({balance}, {amount})");
+                } else {
+                    // Unfunded: the fix refuses...
+                    let err = fixed.unwrap_err();
+                    assert!(
+                        format!("{}", err).contains("exceeds the vault balance"),
+                        "({balance}, {amount}): {err}"
+                    );
+
+                    // ...while the vuln's unchecked operator either wraps
+                    // (an unchecked release build — the exploit) or panics
+                    // (a checked build — a DoS, but never a clean error).
+                    let outcome = std::panic::catch_unwind(|| {
+                        unsafe_arithmetic_vuln::apply_withdraw(
+                            std::hint::black_box(balance),
+                            std::hint::black_box(amount),
+                        )
+                    });
+                    if overflow_checks_enabled {
+                        assert!(outcome.is_err(), "({balance}, {amount}) must panic");
+                    } else {
+                        assert_eq!(outcome.unwrap(), balance.wrapping_sub(amount));
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,82 @@
+//! Guards against copy-paste id collisions between programs.
+//!
+//! Every program in this workspace starts life as a copy of a sibling (vuln →
+//! fix → attacker), so it is easy to forget to regenerate `declare_id!`. Two
+//! programs sharing an id breaks CPI targeting and makes an attacker program
+//! deployable in place of its victim. This test walks the workspace sources,
+//! collects every `declare_id!` value, and asserts they are all distinct.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Extracts the base58 literal from every `declare_id!("...")` in `source`.
+fn declared_ids(source: &str) -> Vec<String> {
+    let mut ids = Vec::new();
+    let mut rest = source;
+    while let Some(start) = rest.find("declare_id!(") {
+        rest = &rest[start + "declare_id!(".len()..];
+        if let Some(open) = rest.find('"') {
+            rest = &rest[open + 1..];
+            if let Some(close) = rest.find('"') {
+                ids.push(rest[..close].to_string());
+                rest = &rest[close + 1..];
+            }
+        }
+    }
+    ids
+}
+
+/// Recursively collects all `.rs` files under `dir`.
+fn rust_sources(dir: &Path, out: &mut Vec<PathBuf>) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            rust_sources(&path, out);
+        } else if path.extension().is_some_and(|ext| ext == "rs") {
+            out.push(path);
+        }
+    }
+}
+
+#[test]
+fn program_ids_are_unique_across_the_workspace() {
+    let workspace_root = Path::new(env!("CARGO_MANIFEST_DIR")).join("..");
+    let programs_dir = workspace_root.join("programs");
+
+    let mut sources = Vec::new();
+    rust_sources(&programs_dir, &mut sources);
+    assert!(
+        !sources.is_empty(),
+        "expected to find program sources under {}",
+        programs_dir.display()
+    );
+
+    // id -> every file that declares it
+    let mut seen: HashMap<String, Vec<PathBuf>> = HashMap::new();
+    for path in sources {
+        let source = fs::read_to_string(&path).expect("source file should be readable");
+        for id in declared_ids(&source) {
+            seen.entry(id).or_default().push(path.clone());
+        }
+    }
+
+    // All 15 programs (5 examples x vuln/fix/attacker) must declare an id.
+    assert!(
+        seen.len() >= 15,
+        "expected at least 15 declared program ids, found {}",
+        seen.len()
+    );
+
+    let collisions: Vec<_> = seen
+        .iter()
+        .filter(|(_, files)| files.len() > 1)
+        .collect();
+    assert!(
+        collisions.is_empty(),
+        "program ids declared more than once: {collisions:#?}"
+    );
+}